@@ -0,0 +1,328 @@
+use std::{
+    error,
+    fmt::{self, Display, Formatter},
+    io,
+    iter,
+    num::ParseIntError,
+    path::Path,
+    str::FromStr,
+};
+
+use aoc::{
+    input,
+    parse::{self, Mode},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Left,
+    Right,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rotation {
+    pub direction: Direction,
+    pub steps: u64,
+}
+
+impl Display for Rotation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let direction = match self.direction {
+            Direction::Left => 'L',
+            Direction::Right => 'R',
+        };
+
+        write!(f, "{direction}{}", self.steps)
+    }
+}
+
+#[derive(Debug)]
+pub enum ParseRotationError {
+    InvalidDirection,
+    InvalidSteps(ParseIntError),
+}
+
+impl Display for ParseRotationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidDirection => write!(f, "Invalid direction"),
+            Self::InvalidSteps(e) => write!(f, "Invalid steps: {e}"),
+        }
+    }
+}
+
+impl error::Error for ParseRotationError {}
+
+impl FromStr for Rotation {
+    type Err = ParseRotationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let direction = match s.chars().next() {
+            Some('L') => Direction::Left,
+            Some('R') => Direction::Right,
+            _ => return Err(ParseRotationError::InvalidDirection),
+        };
+
+        let steps = s[1..].parse().map_err(ParseRotationError::InvalidSteps)?;
+
+        Ok(Rotation { direction, steps })
+    }
+}
+
+pub fn read_rotations(path: &Path, mode: Mode) -> io::Result<Vec<Rotation>> {
+    parse::records(&input::read(path)?, mode)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Event {
+    /// The dial pointed at zero during the rotation, before its last click.
+    PassedZero,
+    /// The dial went all the way around once.
+    FullRevolution,
+    /// The dial points at zero once the rotation is done.
+    LandedOnZero,
+}
+
+/// Outcome of a single rotation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Turn {
+    pub from: u64,
+    pub to: u64,
+    pub steps: u64,
+    /// Number of clicks after which the dial pointed at zero.
+    pub zero_clicks: u64,
+    pub revolutions: u64,
+}
+
+impl Turn {
+    pub fn landed_on_zero(&self) -> bool {
+        self.to == 0
+    }
+
+    pub fn passed_zero(&self) -> u64 {
+        self.zero_clicks - (self.steps > 0 && self.landed_on_zero()) as u64
+    }
+
+    /// Events of the rotation, grouped by kind.
+    pub fn events(&self) -> impl Iterator<Item = Event> {
+        iter::repeat_n(Event::PassedZero, self.passed_zero() as usize)
+            .chain(iter::repeat_n(Event::FullRevolution, self.revolutions as usize))
+            .chain(self.landed_on_zero().then_some(Event::LandedOnZero))
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum Rule {
+    /// Count rotations that leave the dial pointing at zero.
+    Landing,
+    /// Count every click that leaves the dial pointing at zero.
+    Clicks,
+}
+
+impl Rule {
+    pub fn count(&self, turn: &Turn) -> u64 {
+        match self {
+            Rule::Landing => turn.landed_on_zero() as u64,
+            Rule::Clicks => turn.zero_clicks,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Dial {
+    size: u64,
+    position: u64,
+}
+
+impl Dial {
+    pub fn new(size: u64, start: u64) -> Self {
+        assert!(size > 0, "dial must have at least one position");
+
+        Dial {
+            size,
+            position: start % size,
+        }
+    }
+
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Applies a rotation in constant time.
+    pub fn rotate(&mut self, rotation: &Rotation) -> Turn {
+        let n = self.size;
+        let from = self.position;
+        let steps = rotation.steps;
+
+        let first = if from == 0 {
+            n
+        } else {
+            match rotation.direction {
+                Direction::Right => n - from,
+                Direction::Left => from,
+            }
+        };
+
+        let zero_clicks = if steps < first { 0 } else { 1 + (steps - first) / n };
+        let to = match rotation.direction {
+            Direction::Right => (from + steps % n) % n,
+            Direction::Left => (from + n - steps % n) % n,
+        };
+
+        self.position = to;
+
+        Turn {
+            from,
+            to,
+            steps,
+            zero_clicks,
+            revolutions: steps / n,
+        }
+    }
+
+    /// Applies a rotation one click at a time, as a reference for [`Dial::rotate`].
+    pub fn simulate(&mut self, rotation: &Rotation) -> Turn {
+        let from = self.position;
+        let mut zero_clicks = 0;
+        let mut revolutions = 0;
+
+        for click in 1..=rotation.steps {
+            self.position = match rotation.direction {
+                Direction::Right => (self.position + 1) % self.size,
+                Direction::Left => (self.position + self.size - 1) % self.size,
+            };
+
+            if self.position == 0 {
+                zero_clicks += 1;
+            }
+
+            if click % self.size == 0 {
+                revolutions += 1;
+            }
+        }
+
+        Turn {
+            from,
+            to: self.position,
+            steps: rotation.steps,
+            zero_clicks,
+            revolutions,
+        }
+    }
+
+    pub fn count(&mut self, rotations: &[Rotation], rule: Rule) -> u64 {
+        rotations.iter().map(|rotation| rule.count(&self.rotate(rotation))).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc::{prop, rng::Rng};
+
+    use super::*;
+
+    #[derive(Clone, Debug)]
+    struct Case {
+        size: u64,
+        start: u64,
+        rotations: Vec<Rotation>,
+    }
+
+    fn generate(rng: &mut Rng) -> Case {
+        let size = rng.between(1, 20);
+        let rotations = (0..rng.between(1, 10))
+            .map(|_| Rotation {
+                direction: if rng.chance(0.5) { Direction::Left } else { Direction::Right },
+                steps: rng.below(4 * size),
+            })
+            .collect();
+
+        Case {
+            size,
+            start: rng.below(size),
+            rotations,
+        }
+    }
+
+    fn shrink(case: &Case) -> Vec<Case> {
+        let mut smaller: Vec<Case> = prop::shrink_list(&case.rotations)
+            .into_iter()
+            .chain(prop::shrink_items(&case.rotations, |rotation| {
+                prop::shrink_number(rotation.steps).into_iter().map(|steps| Rotation { steps, ..*rotation }).collect()
+            }))
+            .filter(|rotations| !rotations.is_empty())
+            .map(|rotations| Case { rotations, ..case.clone() })
+            .collect();
+
+        // Wrapped, or kept as close below zero as it was
+        for size in prop::shrink_number(case.size).into_iter().filter(|&size| size > 0) {
+            for start in [case.start % size, case.start.min(size - 1)] {
+                smaller.push(Case {
+                    size,
+                    start,
+                    rotations: case.rotations.clone(),
+                });
+            }
+        }
+
+        for start in prop::shrink_number(case.start) {
+            smaller.push(Case { start, ..case.clone() });
+        }
+
+        smaller
+    }
+
+    /// The closed form agrees with the click-by-click simulation on every rotation, and so on the count of part b.
+    fn agrees(case: &Case) -> Result<(), String> {
+        let mut fast = Dial::new(case.size, case.start);
+        let mut slow = fast.clone();
+        let mut clicks = 0;
+
+        for rotation in &case.rotations {
+            let expected = slow.simulate(rotation);
+            let actual = fast.rotate(rotation);
+
+            if expected != actual {
+                return Err(format!("after {rotation}, simulated {expected:?}, closed form {actual:?}"));
+            }
+
+            clicks += expected.zero_clicks;
+        }
+
+        let counted = Dial::new(case.size, case.start).count(&case.rotations, Rule::Clicks);
+
+        if counted != clicks {
+            return Err(format!("simulated {clicks} clicks at zero, counted {counted}"));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn rotate_agrees_with_simulate() {
+        let result = prop::check(&mut Rng::new(1), 10_000, generate, shrink, agrees);
+
+        if let Err(failure) = result {
+            let rotations: Vec<String> = failure.shrunk.rotations.iter().map(Rotation::to_string).collect();
+
+            panic!(
+                "dial of {} at {}: {}: {}",
+                failure.shrunk.size,
+                failure.shrunk.start,
+                rotations.join(" "),
+                failure.reason
+            );
+        }
+    }
+
+    #[test]
+    fn counts_the_example() {
+        let rotations: Vec<Rotation> = "L68 L30 R48 L5 R60 L55 L1 L99 R14 L82"
+            .split(' ')
+            .map(|rotation| rotation.parse().unwrap())
+            .collect();
+
+        assert_eq!(Dial::new(100, 50).count(&rotations, Rule::Landing), 3);
+        assert_eq!(Dial::new(100, 50).count(&rotations, Rule::Clicks), 6);
+    }
+}
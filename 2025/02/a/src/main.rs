@@ -1,8 +1,10 @@
 use std::{
-    fs::read_to_string,
-    io::{self}
+    io::{self},
+    path::Path,
 };
 
+use aoc::{input, parse::Mode};
+
 fn count_invalid(a: u64, b: u64, n: u32) -> u64 {
     let m = n / 2;
     let mut sum= 0;
@@ -19,14 +21,45 @@ fn count_invalid(a: u64, b: u64, n: u32) -> u64 {
     sum
 }
 
+fn parse_ranges(contents: &str, mode: Mode) -> io::Result<Vec<(&str, &str)>> {
+    let mut lines = contents.lines();
+    let mut ranges = vec![];
+
+    for part in lines.next().unwrap_or_default().split(',') {
+        let Some((a, b)) = part.split_once('-') else {
+            mode.reject(1, format!("invalid range `{part}`"))?;
+            continue;
+        };
+
+        let is_number = |s: &str| s.bytes().all(|c| c.is_ascii_digit()) && s.parse::<u64>().is_ok();
+
+        if !is_number(a) || !is_number(b) {
+            mode.reject(1, format!("invalid number in range `{part}`"))?;
+            continue;
+        }
+
+        if a.parse::<u64>().unwrap() > b.parse::<u64>().unwrap() {
+            mode.reject(1, format!("range `{part}` ends before it starts"))?;
+            continue;
+        }
+
+        ranges.push((a, b));
+    }
+
+    for (i, line) in lines.enumerate() {
+        if !line.is_empty() {
+            mode.reject(i + 2, format!("trailing content `{line}`"))?;
+        }
+    }
+
+    Ok(ranges)
+}
+
 fn main() -> io::Result<()> {
-    let contents = read_to_string("../input")?;
+    let contents = input::read(Path::new("../input"))?;
     let mut sum = 0;
     
-    for part in contents.trim().split(',') {
-        let mut iter= part.split("-");
-        let a = iter.next().unwrap();
-        let b = iter.next().unwrap();
+    for (a, b) in parse_ranges(&contents, Mode::from_args())? {
         let m = a.len() as u32;
         let n = b.len() as u32;
         let a: u64 = a.parse().unwrap();
@@ -1,9 +1,13 @@
 use std::{
-    fs::File,
-    io::{self, BufRead},
+    io,
     path::Path
 };
 
+use aoc::{input, parallel::Pool, parse::Mode};
+
+/// Number of batteries turned on in every bank.
+const N: usize = 2;
+
 fn find_max(chars: &[char], offset: usize, length: usize) -> (usize, u32) {
     let mut max = chars.get(offset).unwrap().to_digit(10).unwrap();
     let mut index= offset;
@@ -20,31 +24,41 @@ fn find_max(chars: &[char], offset: usize, length: usize) -> (usize, u32) {
     (index, max)
 }
 
+fn joltage(chars: &[char]) -> u64 {
+    let len = chars.len();
+    let mut joltage = 0_u64;
+    let mut offset = 0;
+
+    for i in 0..N {
+        let remaining = N - i - 1;
+        let (start, number) = find_max(chars, offset, len - offset - remaining);
+
+        joltage = joltage * 10 + number as u64;
+        offset = start + 1;
+    }
+
+    joltage
+}
+
 fn main() -> io::Result<()> {
     let path = Path::new("../input");
-    let file = File::open(path)?;
-    let reader = io::BufReader::new(file);
-    let mut sum = 0;
-
-    for line in reader.lines() {
-        let line = line?;
-        let len = line.len();
-        let chars: Vec<char> = line.chars().collect();
-        let mut joltage = 0_u64;
-        let mut offset = 0;
-        let n = 2;
-
-        for i in 0..n {
-            let remaining = n - i - 1;
-            let (start, number) = find_max(&chars, offset, len - offset - remaining);
-
-            joltage = joltage * 10 + number as u64;
-            offset = start + 1;
+    let mode = Mode::from_args();
+    let mut banks = vec![];
+
+    for (i, line) in input::read(path)?.lines().enumerate() {
+        if line.len() < N || !line.chars().all(|c| c.is_ascii_digit()) {
+            mode.reject(i + 1, format!("invalid battery bank `{line}`"))?;
+            continue;
         }
 
-        sum += joltage;
+        banks.push(line.chars().collect::<Vec<char>>());
     }
 
+    let solved = Pool::from_args()?.map(&banks, |bank| joltage(bank));
+    let sum: u64 = solved.results.iter().sum();
+
+    solved.report();
+
     println!("{sum}");
 
     Ok(())
@@ -1,9 +1,14 @@
 use std::{
-    fs::File,
-    io::{self, BufRead},
+    io,
     path::Path
 };
 
+use aoc::{
+    grid::BitGrid,
+    input::{self, Ragged},
+    parse::Mode,
+};
+
 struct Position {
     x: usize,
     y: usize,
@@ -24,53 +29,38 @@ impl Position {
 
 #[derive(Clone)]
 struct Shelf {
-    data: Vec<char>,
+    rolls: BitGrid,
     width: usize,
     height: usize
 }
 
 impl Shelf {
-    fn new(path: &Path) -> io::Result<Self> {
-        let file = File::open(path)?;
-        let reader = io::BufReader::new(file);
-        let mut width= 0;
-        let mut data: Vec<char> = vec![];
-        let mut height = 0;
-
-        for line in reader.lines() {
-            let line = line?;
-
-            if width == 0 {
-                width = line.len();
-            }
-
-            for c in line.chars() {
-                data.push(c);
+    fn new(path: &Path, mode: Mode) -> io::Result<Self> {
+        let rows = input::rows(&input::read(path)?, Ragged::Reject, mode)?;
+        let width = rows.first().map_or(0, |row| row.cells.len());
+        let height = rows.len();
+        let mut rolls = BitGrid::new(width, height);
+
+        for (y, row) in rows.into_iter().enumerate() {
+            for (x, c) in row.cells.into_iter().enumerate() {
+                match c {
+                    '@' => rolls.set(x, y, true),
+                    '.' => {}
+                    _ => mode.reject(row.line, format!("unknown cell '{c}', treating it as empty"))?,
+                }
             }
-
-            height += 1;
         }
 
-        Ok(Self { data, width, height })
+        Ok(Self { rolls, width, height })
     }
 
-    fn get(&self, p: &Position) -> Option<&char> {
-        if p.x >= self.width {
-            return None;
-        }
-
-        self.data.get(p.y * self.width + p.x)
+    fn get(&self, p: &Position) -> Option<bool> {
+        self.rolls.get(p.x, p.y)
     }
 
 
-    fn set(&mut self, p: &Position, c: char) {
-        if p.x >= self.width {
-            return;
-        }
-
-        if let Some(it) = self.data.get_mut(p.y * self.width + p.x) {
-            *it = c;
-        }
+    fn set(&mut self, p: &Position, roll: bool) {
+        self.rolls.set(p.x, p.y, roll);
     }
 
     fn can_remove(&self, p: &Position) -> bool {
@@ -82,13 +72,13 @@ impl Shelf {
                     continue
                 }
 
-                if let Some(neighbour) = p.offset(dx, dy) {
-                    if let Some('@') = self.get(&neighbour) {
-                        sum += 1;
+                if let Some(neighbour) = p.offset(dx, dy)
+                    && let Some(true) = self.get(&neighbour)
+                {
+                    sum += 1;
 
-                        if sum == 4 {
-                            return false;
-                        }
+                    if sum == 4 {
+                        return false;
                     }
                 }
             }
@@ -101,7 +91,7 @@ impl Shelf {
 
 
 fn main() -> io::Result<()> {
-    let mut shelf = Shelf::new(Path::new("../input"))?;
+    let mut shelf = Shelf::new(Path::new("../input"), Mode::from_args())?;
     let mut count = 0;
 
     loop {
@@ -112,13 +102,13 @@ fn main() -> io::Result<()> {
             for x in 0..shelf.width {
                 let p = Position { x, y };
 
-                let Some('@') = shelf.get(&p) else {
+                let Some(true) = shelf.get(&p) else {
                     continue;
                 };
 
 
                 if shelf.can_remove(&p) {
-                    other.set(&p, '.');
+                    other.set(&p, false);
                     removed += 1;
                 }
             }
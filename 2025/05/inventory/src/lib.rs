@@ -0,0 +1,146 @@
+use std::{cmp::Ordering, io, path::Path};
+
+use aoc::{input, parse::Mode};
+
+pub type Interval = (u64, u64);
+
+/// The fresh ID ranges, merged and sorted, and the available ingredient IDs in input order.
+pub struct Inventory {
+    pub fresh: Vec<Interval>,
+    pub ingredients: Vec<u64>,
+}
+
+impl Inventory {
+    pub fn is_fresh(&self, id: u64) -> bool {
+        self.fresh
+            .binary_search_by(|it| {
+                if it.1 < id {
+                    Ordering::Less
+                } else if it.0 > id {
+                    Ordering::Greater
+                } else {
+                    Ordering::Equal
+                }
+            })
+            .is_ok()
+    }
+}
+
+fn add_range(intervals: &mut Vec<Interval>, range: &Interval) {
+    let i = intervals.partition_point(|it| it.1 < range.0);
+    let j = intervals.partition_point(|it| it.0 <= range.1);
+
+    if i == j {
+        intervals.insert(i, *range);
+        return;
+    }
+
+    let new_start = range.0.min(intervals[i].0);
+    let new_end = range.1.max(intervals[j - 1].1);
+    intervals.splice(i..j, [(new_start, new_end)]);
+}
+
+fn parse_range(line: &str) -> Result<Interval, String> {
+    let Some((a, b)) = line.split_once('-') else {
+        return Err(format!("invalid range format `{line}`"));
+    };
+
+    let start: u64 = a.parse().map_err(|_| format!("invalid range start `{a}` in `{line}`"))?;
+    let end: u64 = b.parse().map_err(|_| format!("invalid range end `{b}` in `{line}`"))?;
+
+    if end < start {
+        return Err(format!("range `{line}` ends before it starts"));
+    }
+
+    Ok((start, end))
+}
+
+/// Parses the ranges, a blank line, then the ingredient IDs. Only malformed lines are rejected: the puzzle allows
+/// overlapping ranges and repeated IDs, which are counted every time they appear.
+pub fn parse_inventory(text: &str, mode: Mode) -> io::Result<Inventory> {
+    let mut inventory = Inventory {
+        fresh: vec![],
+        ingredients: vec![],
+    };
+    let mut interval_mode = true;
+
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+
+        if line.is_empty() {
+            if !interval_mode {
+                mode.reject(i + 1, "unexpected blank line after the ingredient section")?;
+            }
+
+            interval_mode = false;
+            continue;
+        }
+
+        if interval_mode {
+            match parse_range(line) {
+                Ok(range) => add_range(&mut inventory.fresh, &range),
+                Err(e) => mode.reject(i + 1, e)?,
+            }
+
+            continue;
+        }
+
+        match line.parse() {
+            Ok(id) => inventory.ingredients.push(id),
+            Err(_) => mode.reject(i + 1, format!("invalid number `{line}`"))?,
+        }
+    }
+
+    Ok(inventory)
+}
+
+pub fn read_inventory(path: &Path, mode: Mode) -> io::Result<Inventory> {
+    parse_inventory(&input::read(path)?, mode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "3-5\n10-14\n16-20\n12-18\n\n1\n5\n8\n11\n17\n32\n";
+
+    fn error(text: &str) -> Option<String> {
+        parse_inventory(text, Mode::Strict).err().map(|e| e.to_string())
+    }
+
+    #[test]
+    fn merges_the_example_ranges() {
+        let inventory = parse_inventory(EXAMPLE, Mode::Strict).unwrap();
+        let fresh = inventory.ingredients.iter().filter(|&&id| inventory.is_fresh(id)).count();
+
+        assert_eq!(inventory.fresh, [(3, 5), (10, 20)]);
+        assert_eq!(fresh, 3);
+    }
+
+    #[test]
+    fn strict_mode_rejects_malformed_lines() {
+        assert_eq!(error("1-3\n3\n\n1\n").as_deref(), Some("line 2: invalid range format `3`"));
+        assert_eq!(error("5-3\n\n1\n").as_deref(), Some("line 1: range `5-3` ends before it starts"));
+        assert_eq!(error("1-3\n\n1\nx\n").as_deref(), Some("line 4: invalid number `x`"));
+        assert_eq!(
+            error("1-3\n\n1\n\n2\n").as_deref(),
+            Some("line 4: unexpected blank line after the ingredient section")
+        );
+    }
+
+    #[test]
+    fn strict_mode_takes_repeats() {
+        let inventory = parse_inventory("1-3\n2-3\n\n2\n2\n", Mode::Strict).unwrap();
+
+        assert_eq!(inventory.fresh, [(1, 3)]);
+        assert_eq!(inventory.ingredients, [2, 2]);
+    }
+
+    #[test]
+    fn lenient_mode_skips_malformed_lines() {
+        let inventory = parse_inventory("1-3\nx\n5-3\n\n2\ny\n\n4\n", Mode::Lenient).unwrap();
+
+        assert_eq!(inventory.fresh, [(1, 3)]);
+        assert_eq!(inventory.ingredients, [2, 4]);
+    }
+}
@@ -0,0 +1,362 @@
+use std::{
+    error::Error,
+    fmt,
+    io,
+    num::ParseIntError,
+    path::Path,
+    str::FromStr,
+};
+
+use aoc::{
+    input::{self, Ragged},
+    parse::{self, Mode},
+};
+
+#[derive(Clone, Copy)]
+pub enum Operation {
+    Add,
+    Multiply,
+}
+
+#[derive(Debug)]
+pub struct ParseOperationError(String);
+
+impl fmt::Display for ParseOperationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Error for ParseOperationError {}
+
+impl Operation {
+    /// `a` and `b` combined, or `None` when the result does not fit in a `u64`.
+    pub fn apply(&self, a: u64, b: u64) -> Option<u64> {
+        match self {
+            Operation::Add => a.checked_add(b),
+            Operation::Multiply => a.checked_mul(b),
+        }
+    }
+
+    pub fn symbol(&self) -> char {
+        match self {
+            Operation::Add => '+',
+            Operation::Multiply => '*',
+        }
+    }
+}
+
+impl FromStr for Operation {
+    type Err = ParseOperationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "*" => Ok(Operation::Multiply),
+            "+" => Ok(Operation::Add),
+            _ => Err(ParseOperationError(format!("Unsupported operation {s}"))),
+        }
+    }
+}
+
+/// How the digits of a problem block are turned into numbers.
+#[derive(Clone, Copy)]
+pub enum Reading {
+    /// Every row is one number, read left to right.
+    Human,
+    /// Every column is one number, read top to bottom, columns taken right to left.
+    Cephalopod,
+}
+
+pub struct Problem {
+    column: usize,
+    rows: Vec<String>,
+    operation: Operation,
+    human: Vec<u64>,
+    cephalopod: Vec<u64>,
+    answers: [u64; 2],
+}
+
+/// The numbers combined with `operation`, 0 when there are none, or `None` on overflow.
+fn evaluate(numbers: &[u64], operation: Operation) -> Option<u64> {
+    match numbers.split_first() {
+        Some((first, rest)) => rest.iter().try_fold(*first, |a, &b| operation.apply(a, b)),
+        None => Some(0),
+    }
+}
+
+/// Numbers of a problem block in `reading`, failing on the first that is not a number.
+fn read_numbers(rows: &[String], reading: Reading) -> Result<Vec<u64>, ParseIntError> {
+    let texts: Vec<String> = match reading {
+        Reading::Human => rows.iter().map(|row| row.trim().to_owned()).collect(),
+        Reading::Cephalopod => {
+            let width = rows.first().map_or(0, |row| row.len());
+
+            (0..width)
+                .rev()
+                .map(|i| {
+                    rows.iter()
+                        .map(|row| row.as_bytes()[i])
+                        .filter(|c| c.is_ascii_digit())
+                        .map(char::from)
+                        .collect()
+                })
+                .collect()
+        }
+    };
+
+    texts.iter().filter(|text| !text.is_empty()).map(|text| text.parse()).collect()
+}
+
+impl Problem {
+    /// Index of the first sheet column covered by this problem.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// The raw characters of the number rows, all padded to the block width.
+    pub fn rows(&self) -> &[String] {
+        &self.rows
+    }
+
+    pub fn operation(&self) -> Operation {
+        self.operation
+    }
+
+    pub fn numbers(&self, reading: Reading) -> Vec<u64> {
+        match reading {
+            Reading::Human => self.human.clone(),
+            Reading::Cephalopod => self.cephalopod.clone(),
+        }
+    }
+
+    pub fn solve(&self, reading: Reading) -> u64 {
+        self.answers[reading as usize]
+    }
+
+    /// Renders the problem as an expression with its result, e.g. `123 * 45 * 6 = 33210`.
+    pub fn explain(&self, reading: Reading) -> String {
+        let separator = format!(" {} ", self.operation.symbol());
+        let expression = self
+            .numbers(reading)
+            .iter()
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(&separator);
+
+        format!("{expression} = {}", self.solve(reading))
+    }
+}
+
+pub struct Worksheet {
+    problems: Vec<Problem>,
+    totals: [u64; 2],
+}
+
+#[derive(Debug)]
+pub enum ParseWorksheetError {
+    Empty,
+    MissingOperation(usize),
+    AmbiguousOperation(usize),
+    InvalidOperation(ParseOperationError),
+    InvalidNumber(usize, ParseIntError),
+    Overflow(usize),
+    TotalOverflow,
+}
+
+impl fmt::Display for ParseWorksheetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "Worksheet is empty"),
+            Self::MissingOperation(column) => write!(f, "Problem at column {column} has no operation"),
+            Self::AmbiguousOperation(column) => write!(f, "Problem at column {column} has more than one operation"),
+            Self::InvalidOperation(e) => write!(f, "Invalid operation: {e}"),
+            Self::InvalidNumber(column, e) => write!(f, "Problem at column {column} has an invalid number: {e}"),
+            Self::Overflow(column) => write!(f, "Problem at column {column} has a result too large for 64 bits"),
+            Self::TotalOverflow => write!(f, "Worksheet total is too large for 64 bits"),
+        }
+    }
+}
+
+impl Error for ParseWorksheetError {}
+
+impl Worksheet {
+    pub fn load(path: &Path, mode: Mode) -> io::Result<Self> {
+        Self::parse(&input::read(path)?, mode)
+    }
+
+    pub fn parse(s: &str, mode: Mode) -> io::Result<Self> {
+        let mut numbers = vec![];
+        let mut operations = None;
+
+        // Problems are aligned by column and trailing spaces may have been stripped, so short rows are padded.
+        for row in input::rows(s, Ragged::Pad(' '), mode)? {
+            let line: String = row.cells.into_iter().collect();
+
+            if line.trim().is_empty() {
+                mode.reject(row.line, "blank line")?;
+                continue;
+            }
+
+            if let Some(c) = line.chars().find(|c| !(c.is_ascii_digit() || " +*".contains(*c))) {
+                mode.reject(row.line, format!("unknown character '{c}'"))?;
+                continue;
+            }
+
+            let is_operations = line.contains(['+', '*']);
+
+            if operations.is_some() {
+                let reason = if is_operations {
+                    "duplicate operations line"
+                } else {
+                    "trailing content after the operations line"
+                };
+
+                mode.reject(row.line, reason)?;
+                continue;
+            }
+
+            if !is_operations {
+                numbers.push(line);
+            } else if line.contains(|c: char| c.is_ascii_digit()) {
+                mode.reject(row.line, "numbers mixed with operations")?;
+            } else {
+                operations = Some(line);
+            }
+        }
+
+        let Some(operations) = operations else {
+            return Err(parse::error(s.lines().count(), "missing operations line"));
+        };
+
+        Self::from_rows(&numbers, &operations).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    fn from_rows(numbers: &[String], operations: &str) -> Result<Self, ParseWorksheetError> {
+        let width = numbers.iter().map(String::as_str).chain([operations]).map(|line| line.len()).max().unwrap_or(0);
+        let cell = |line: &str, i: usize| line.as_bytes().get(i).copied().unwrap_or(b' ');
+        let blank = |i: usize| numbers.iter().map(String::as_str).chain([operations]).all(|line| cell(line, i) == b' ');
+        let mut problems = vec![];
+        let mut i = 0;
+
+        while i < width {
+            if blank(i) {
+                i += 1;
+                continue;
+            }
+
+            let start = i;
+
+            while i < width && !blank(i) {
+                i += 1;
+            }
+
+            let symbols: Vec<u8> = (start..i).map(|k| cell(operations, k)).filter(|c| *c != b' ').collect();
+            let operation = match symbols.as_slice() {
+                [] => return Err(ParseWorksheetError::MissingOperation(start)),
+                [symbol] => char::from(*symbol)
+                    .to_string()
+                    .parse()
+                    .map_err(ParseWorksheetError::InvalidOperation)?,
+                _ => return Err(ParseWorksheetError::AmbiguousOperation(start)),
+            };
+
+            let rows: Vec<String> = numbers
+                .iter()
+                .map(|line| (start..i).map(|k| char::from(cell(line, k))).collect())
+                .collect();
+
+            // Both readings are parsed up front, so a number too large or split by a space is refused here
+            let invalid = |e| ParseWorksheetError::InvalidNumber(start, e);
+            let human = read_numbers(&rows, Reading::Human).map_err(invalid)?;
+            let cephalopod = read_numbers(&rows, Reading::Cephalopod).map_err(invalid)?;
+
+            // So are results too large, leaving solving and totalling nothing that can fail
+            let answers = [evaluate(&human, operation), evaluate(&cephalopod, operation)];
+            let [Some(human_answer), Some(cephalopod_answer)] = answers else {
+                return Err(ParseWorksheetError::Overflow(start));
+            };
+
+            problems.push(Problem {
+                column: start,
+                rows,
+                operation,
+                human,
+                cephalopod,
+                answers: [human_answer, cephalopod_answer],
+            });
+        }
+
+        if problems.is_empty() {
+            return Err(ParseWorksheetError::Empty);
+        }
+
+        let total = |reading: Reading| {
+            problems.iter().try_fold(0_u64, |total, problem| total.checked_add(problem.solve(reading)))
+        };
+        let [Some(human), Some(cephalopod)] = [total(Reading::Human), total(Reading::Cephalopod)] else {
+            return Err(ParseWorksheetError::TotalOverflow);
+        };
+
+        Ok(Worksheet {
+            problems,
+            totals: [human, cephalopod],
+        })
+    }
+
+    pub fn problems(&self) -> &[Problem] {
+        &self.problems
+    }
+
+    pub fn total(&self, reading: Reading) -> u64 {
+        self.totals[reading as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "123 328  51 64 \n 45 64  387 23 \n  6 98  215 314\n*   +   *   +  \n";
+
+    fn error(s: &str) -> String {
+        Worksheet::parse(s, Mode::Strict).err().unwrap().to_string()
+    }
+
+    #[test]
+    fn solves_the_example() {
+        let worksheet = Worksheet::parse(EXAMPLE, Mode::Strict).unwrap();
+
+        assert_eq!(worksheet.total(Reading::Human), 4277556);
+        assert_eq!(worksheet.total(Reading::Cephalopod), 3263827);
+        assert_eq!(worksheet.problems()[0].explain(Reading::Human), "123 * 45 * 6 = 33210");
+        assert_eq!(worksheet.problems()[3].explain(Reading::Cephalopod), "4 + 431 + 623 = 1058");
+    }
+
+    #[test]
+    fn checks_operations_for_overflow() {
+        assert_eq!(Operation::Add.apply(u64::MAX - 1, 1), Some(u64::MAX));
+        assert_eq!(Operation::Add.apply(u64::MAX, 1), None);
+        assert_eq!(Operation::Multiply.apply(1 << 32, 1 << 31), Some(1 << 63));
+        assert_eq!(Operation::Multiply.apply(1 << 32, 1 << 32), None);
+    }
+
+    #[test]
+    fn refuses_results_too_large_for_64_bits() {
+        // Every number fits, only their product does not
+        assert_eq!(
+            error("4294967296\n4294967296\n*\n"),
+            "Problem at column 0 has a result too large for 64 bits"
+        );
+        // Read by rows the product is 0, by columns it is three ten-digit numbers
+        let sheet = format!("{}000\n*\n", "111\n".repeat(9));
+
+        assert_eq!(error(&sheet), "Problem at column 0 has a result too large for 64 bits");
+        assert_eq!(
+            error("18446744073709551615 1\n*                    +\n"),
+            "Worksheet total is too large for 64 bits"
+        );
+        let largest = Worksheet::parse("18446744073709551615\n*\n", Mode::Strict).unwrap();
+
+        assert_eq!(largest.total(Reading::Human), u64::MAX);
+    }
+}
@@ -0,0 +1,425 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    error,
+    fmt::{self, Display, Formatter},
+    io,
+    path::Path,
+};
+
+use aoc::{
+    input::{self, Ragged},
+    parse::Mode,
+};
+
+pub struct Grid<T: Copy> {
+    pub width: usize,
+    pub height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Copy> Grid<T> {
+    pub fn new(width: usize, height: usize, init: T) -> Self {
+        let cells = vec![init; width * height];
+
+        Self { width, height, cells }
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> Option<T> {
+        self.cells.get(self.width * j + i).copied()
+    }
+
+    pub fn set(&mut self, i: usize, j: usize, c: T) {
+        if let Some(it) = self.cells.get_mut(self.width * j + i) {
+            *it = c;
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    const ALL: [Direction; 4] = [Direction::Up, Direction::Down, Direction::Left, Direction::Right];
+
+    pub fn delta(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+        }
+    }
+}
+
+/// A beam leaving a cell: it enters the cell at `offset` from the current one, moving in `heading`.
+#[derive(Clone, Copy)]
+pub struct Beam {
+    pub offset: (isize, isize),
+    pub heading: Direction,
+}
+
+impl Beam {
+    pub fn forward(heading: Direction) -> Self {
+        Beam {
+            offset: heading.delta(),
+            heading,
+        }
+    }
+}
+
+pub trait Cell {
+    /// Beams leaving this cell after a beam moving in `heading` has entered it.
+    fn interact(&self, heading: Direction) -> Vec<Beam>;
+
+    /// Headings of the beams this cell emits by itself.
+    fn emit(&self) -> Vec<Direction> {
+        vec![]
+    }
+}
+
+pub struct Empty;
+
+static EMPTY: Empty = Empty;
+
+impl Cell for Empty {
+    fn interact(&self, heading: Direction) -> Vec<Beam> {
+        vec![Beam::forward(heading)]
+    }
+}
+
+pub struct Source(pub Direction);
+
+impl Cell for Source {
+    fn interact(&self, heading: Direction) -> Vec<Beam> {
+        vec![Beam::forward(heading)]
+    }
+
+    fn emit(&self) -> Vec<Direction> {
+        vec![self.0]
+    }
+}
+
+/// Stops the incoming beam and continues it from both neighbours perpendicular to its heading.
+pub struct Splitter;
+
+impl Cell for Splitter {
+    fn interact(&self, heading: Direction) -> Vec<Beam> {
+        let (dx, dy) = heading.delta();
+
+        vec![
+            Beam {
+                offset: (-dy, -dx),
+                heading,
+            },
+            Beam { offset: (dy, dx), heading },
+        ]
+    }
+}
+
+pub enum Mirror {
+    /// `/`
+    Slash,
+    /// `\`
+    Backslash,
+}
+
+impl Cell for Mirror {
+    fn interact(&self, heading: Direction) -> Vec<Beam> {
+        let turned = match (self, heading) {
+            (Mirror::Slash, Direction::Up) | (Mirror::Backslash, Direction::Down) => Direction::Right,
+            (Mirror::Slash, Direction::Down) | (Mirror::Backslash, Direction::Up) => Direction::Left,
+            (Mirror::Slash, Direction::Left) | (Mirror::Backslash, Direction::Right) => Direction::Down,
+            (Mirror::Slash, Direction::Right) | (Mirror::Backslash, Direction::Left) => Direction::Up,
+        };
+
+        vec![Beam::forward(turned)]
+    }
+}
+
+pub struct Absorber;
+
+impl Cell for Absorber {
+    fn interact(&self, _: Direction) -> Vec<Beam> {
+        vec![]
+    }
+}
+
+/// Maps input characters to cell types.
+pub struct Palette {
+    cells: HashMap<char, Box<dyn Cell>>,
+}
+
+impl Palette {
+    pub fn new() -> Self {
+        Palette { cells: HashMap::new() }
+    }
+
+    pub fn register(&mut self, c: char, cell: impl Cell + 'static) {
+        self.cells.insert(c, Box::new(cell));
+    }
+
+    pub fn with(mut self, c: char, cell: impl Cell + 'static) -> Self {
+        self.register(c, cell);
+        self
+    }
+
+    fn get(&self, c: char) -> Option<&dyn Cell> {
+        self.cells.get(&c).map(|cell| cell.as_ref())
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Palette::new()
+            .with('.', Empty)
+            .with('S', Source(Direction::Down))
+            .with('^', Splitter)
+            .with('/', Mirror::Slash)
+            .with('\\', Mirror::Backslash)
+            .with('#', Absorber)
+    }
+}
+
+#[derive(Debug)]
+pub enum ManifoldError {
+    Cycle(usize, usize),
+}
+
+impl Display for ManifoldError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Cycle(x, y) => write!(f, "Beam loops forever through [{x}, {y}]"),
+        }
+    }
+}
+
+impl error::Error for ManifoldError {}
+
+pub struct Propagation {
+    /// Number of distinct cells where a beam was split.
+    pub splits: usize,
+    /// Number of beams, counted with multiplicity, that left the manifold.
+    pub timelines: u64,
+    /// Number of beams, counted with multiplicity, that entered each cell.
+    pub intensity: Grid<u64>,
+}
+
+pub struct Manifold<'a> {
+    width: usize,
+    height: usize,
+    cells: Vec<&'a dyn Cell>,
+}
+
+type State = (usize, Direction);
+
+impl<'a> Manifold<'a> {
+    pub fn load(path: &Path, palette: &'a Palette, mode: Mode) -> io::Result<Self> {
+        Self::parse(&input::read(path)?, palette, mode)
+    }
+
+    pub fn parse(s: &str, palette: &'a Palette, mode: Mode) -> io::Result<Self> {
+        let rows = input::rows(s, Ragged::Reject, mode)?;
+        let width = rows.first().map_or(0, |row| row.cells.len());
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+
+        for row in &rows {
+            for (x, &c) in row.cells.iter().enumerate() {
+                match palette.get(c) {
+                    Some(cell) => cells.push(cell),
+                    None => {
+                        mode.reject(row.line, format!("unknown cell '{c}' at column {}, treating it as empty", x + 1))?;
+                        cells.push(&EMPTY);
+                    }
+                }
+            }
+        }
+
+        Ok(Manifold { width, height, cells })
+    }
+
+    fn step(&self, index: usize, beam: &Beam) -> Option<State> {
+        let x = (index % self.width).checked_add_signed(beam.offset.0)?;
+        let y = (index / self.width).checked_add_signed(beam.offset.1)?;
+
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        Some((self.width * y + x, beam.heading))
+    }
+
+    /// Propagates all beams from the sources in a single pass over the reachable beam states.
+    pub fn propagate(&self) -> Result<Propagation, ManifoldError> {
+        let mut intensity = Grid::new(self.width, self.height, 0_u64);
+        let mut timelines = 0;
+        let mut incoming: HashMap<State, u64> = HashMap::new();
+
+        for (index, cell) in self.cells.iter().enumerate() {
+            for heading in cell.emit() {
+                self.set_intensity(&mut intensity, index, 1);
+
+                match self.step(index, &Beam::forward(heading)) {
+                    Some(state) => *incoming.entry(state).or_default() += 1,
+                    None => timelines += 1,
+                }
+            }
+        }
+
+        let mut outputs: HashMap<State, Vec<Option<State>>> = HashMap::new();
+        let mut in_degree: HashMap<State, usize> = HashMap::new();
+        let mut stack: Vec<State> = incoming.keys().copied().collect();
+
+        while let Some(state) = stack.pop() {
+            if outputs.contains_key(&state) {
+                continue;
+            }
+
+            let targets = self.cells[state.0]
+                .interact(state.1)
+                .iter()
+                .map(|beam| self.step(state.0, beam))
+                .collect::<Vec<_>>();
+
+            for target in targets.iter().flatten() {
+                *in_degree.entry(*target).or_default() += 1;
+                stack.push(*target);
+            }
+
+            outputs.insert(state, targets);
+        }
+
+        let mut queue: VecDeque<State> = outputs
+            .keys()
+            .filter(|state| !in_degree.contains_key(state))
+            .copied()
+            .collect();
+        let mut split_cells = vec![false; self.cells.len()];
+        let mut processed = 0;
+
+        while let Some(state) = queue.pop_front() {
+            let count = incoming.get(&state).copied().unwrap_or(0);
+            let targets = &outputs[&state];
+
+            processed += 1;
+            self.set_intensity(&mut intensity, state.0, count);
+
+            if targets.len() > 1 {
+                split_cells[state.0] = true;
+            }
+
+            for target in targets {
+                let Some(target) = target else {
+                    timelines += count;
+                    continue;
+                };
+
+                *incoming.entry(*target).or_default() += count;
+
+                let degree = in_degree.get_mut(target).unwrap();
+                *degree -= 1;
+
+                if *degree == 0 {
+                    queue.push_back(*target);
+                }
+            }
+        }
+
+        if processed < outputs.len() {
+            let (index, _) = Direction::ALL
+                .iter()
+                .flat_map(|heading| (0..self.cells.len()).map(move |index| (index, *heading)))
+                .find(|state| in_degree.get(state).is_some_and(|degree| *degree > 0))
+                .unwrap();
+
+            return Err(ManifoldError::Cycle(index % self.width, index / self.width));
+        }
+
+        Ok(Propagation {
+            splits: split_cells.into_iter().filter(|split| *split).count(),
+            timelines,
+            intensity,
+        })
+    }
+
+    fn set_intensity(&self, intensity: &mut Grid<u64>, index: usize, count: u64) {
+        let (x, y) = (index % self.width, index / self.width);
+        let before = intensity.get(x, y).unwrap();
+
+        intensity.set(x, y, before + count);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+.......S.......
+...............
+.......^.......
+...............
+......^.^......
+...............
+.....^.^.^.....
+...............
+....^.^...^....
+...............
+...^.^...^.^...
+...............
+..^...^.....^..
+...............
+.^.^.^.^.^...^.
+...............
+";
+
+    fn propagate(s: &str) -> Result<Propagation, ManifoldError> {
+        let palette = Palette::default();
+
+        Manifold::parse(s, &palette, Mode::Strict).unwrap().propagate()
+    }
+
+    #[test]
+    fn counts_the_example_splits_and_timelines() {
+        let propagation = propagate(EXAMPLE).unwrap();
+
+        assert_eq!((propagation.splits, propagation.timelines), (21, 40));
+        assert_eq!(propagation.intensity.get(7, 0), Some(1));
+        assert_eq!((0..15).filter_map(|x| propagation.intensity.get(x, 15)).sum::<u64>(), 40);
+    }
+
+    #[test]
+    fn follows_mirrors_and_stops_at_absorbers() {
+        // Turned right along the bottom row, then up and out of the top
+        let propagation = propagate("S..\n\\./\n").unwrap();
+
+        assert_eq!((propagation.splits, propagation.timelines), (0, 1));
+        assert_eq!(propagation.intensity.get(2, 0), Some(1));
+
+        let propagation = propagate("S.#\n\\./\n").unwrap();
+
+        assert_eq!((propagation.splits, propagation.timelines), (0, 0));
+    }
+
+    #[test]
+    fn reports_beams_that_loop_forever() {
+        let error = propagate(".....\n./.\\.\n...S.\n.\\./.\n.....\n").err().unwrap();
+
+        assert!(matches!(error, ManifoldError::Cycle(..)), "{error}");
+    }
+
+    #[test]
+    fn reads_cells_from_a_custom_palette() {
+        let palette = Palette::new().with('.', Empty).with('v', Source(Direction::Down)).with('x', Splitter);
+        let manifold = Manifold::parse(".v.\n.x.\n...\n", &palette, Mode::Strict).unwrap();
+
+        assert_eq!(manifold.propagate().unwrap().timelines, 2);
+        assert!(Manifold::parse(".S.\n", &palette, Mode::Strict).is_err());
+        assert!(Manifold::parse(".S.\n", &palette, Mode::Lenient).is_ok());
+    }
+}
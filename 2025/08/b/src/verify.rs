@@ -0,0 +1,54 @@
+//! Checks the final connection by looking at which junction boxes are within reach of each other, without sorting
+//! pairs or merging circuits.
+
+use cluster::Point;
+
+/// Labels every point with its component in the graph joining points closer than `limit`, or at most `limit` apart
+/// when `inclusive`.
+fn components(points: &[Point], limit: u64, inclusive: bool) -> Vec<usize> {
+    let mut label = vec![usize::MAX; points.len()];
+
+    for start in 0..points.len() {
+        if label[start] != usize::MAX {
+            continue;
+        }
+
+        let mut stack = vec![start];
+        label[start] = start;
+
+        while let Some(i) = stack.pop() {
+            for j in 0..points.len() {
+                let distance = points[i].distance_to(&points[j]);
+                let near = distance < limit || (inclusive && distance == limit);
+
+                if near && label[j] == usize::MAX {
+                    label[j] = start;
+                    stack.push(j);
+                }
+            }
+        }
+    }
+
+    label
+}
+
+/// Product of the X coordinates of `a` and `b`, if connecting them is what finally joins every box into one circuit.
+pub fn last_connection(points: &[Point], a: usize, b: usize) -> Result<i64, String> {
+    if a == b || a >= points.len() || b >= points.len() {
+        return Err(format!("{a} and {b} are not two distinct junction boxes"));
+    }
+
+    let limit = points[a].distance_to(&points[b]);
+    let before = components(points, limit, false);
+    let after = components(points, limit, true);
+
+    if before[a] == before[b] {
+        return Err(format!("{} and {} are already connected by shorter cables", points[a], points[b]));
+    }
+
+    if after.iter().any(|label| *label != after[0]) {
+        return Err(format!("connecting {} and {} leaves more than one circuit", points[a], points[b]));
+    }
+
+    Ok(points[a].x * points[b].x)
+}
@@ -0,0 +1,393 @@
+use std::{
+    error,
+    fmt::{self, Display, Formatter},
+    io,
+    num::ParseIntError,
+    path::Path,
+    str::FromStr,
+};
+
+use aoc::{
+    input,
+    parse::{self, Mode},
+};
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}, {}, {}]", self.x, self.y, self.z)
+    }
+}
+
+impl Point {
+    /// Squared euclidean distance, which keeps the ordering exact.
+    pub fn distance_to(&self, other: &Point) -> u64 {
+        ((self.x - other.x).pow(2) + (self.y - other.y).pow(2) + (self.z - other.z).pow(2)) as u64
+    }
+}
+
+#[derive(Debug)]
+pub enum ParsePointError {
+    WrongFieldCount,
+    InvalidNumber(ParseIntError),
+}
+
+impl Display for ParsePointError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongFieldCount => write!(f, "Wrong field count"),
+            Self::InvalidNumber(e) => write!(f, "Invalid number: {e}"),
+        }
+    }
+}
+
+impl error::Error for ParsePointError {}
+
+impl FromStr for Point {
+    type Err = ParsePointError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split(",").collect();
+
+        if parts.len() != 3 {
+            return Err(ParsePointError::WrongFieldCount);
+        }
+
+        let coordinates: Vec<i64> = parts
+            .into_iter()
+            .map(|it| it.parse::<i64>().map_err(ParsePointError::InvalidNumber))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Point {
+            x: coordinates[0],
+            y: coordinates[1],
+            z: coordinates[2],
+        })
+    }
+}
+
+trait DisjointSetUnion {
+    fn find(&mut self, i: usize) -> usize;
+    fn union(&mut self, i: usize, j: usize);
+}
+
+impl DisjointSetUnion for Vec<usize> {
+    fn find(&mut self, i: usize) -> usize {
+        if self[i] != i {
+            self[i] = self.find(self[i])
+        }
+
+        self[i]
+    }
+
+    fn union(&mut self, i: usize, j: usize) {
+        let p = self.find(i);
+        let q = self.find(j);
+
+        if p == q {
+            return;
+        }
+
+        self[q] = p;
+    }
+}
+
+pub fn read_points(path: &Path, mode: Mode) -> io::Result<Vec<Point>> {
+    parse::records(&input::read(path)?, mode)
+}
+
+fn calculate_pairwise_distances(points: &[Point]) -> Vec<(usize, usize, u64)> {
+    let pair_count = points.len().saturating_mul(points.len().saturating_sub(1)) / 2;
+    let mut distances: Vec<(usize, usize, u64)> = Vec::with_capacity(pair_count);
+
+    for i in 0..points.len().saturating_sub(1) {
+        for j in i + 1..points.len() {
+            distances.push((i, j, points[i].distance_to(&points[j])));
+        }
+    }
+
+    distances.sort_by_key(|p| p.2);
+    distances
+}
+
+/// One step of single-linkage clustering: the connection that joined two circuits.
+pub struct Merge {
+    /// Position of the connection in the list of pairs ordered by distance.
+    pub connection: usize,
+    pub a: usize,
+    pub b: usize,
+    /// Squared distance between `a` and `b`.
+    pub distance: u64,
+    /// Size of the circuit created by this merge.
+    pub size: usize,
+    /// Dendrogram nodes joined by this merge: leaves are point indices, merge `k` is node `points + k`.
+    pub left: usize,
+    pub right: usize,
+}
+
+pub struct Dendrogram {
+    points: usize,
+    merges: Vec<Merge>,
+}
+
+impl Dendrogram {
+    pub fn build(points: &[Point]) -> Self {
+        let distances = calculate_pairwise_distances(points);
+        let mut connections: Vec<_> = (0..points.len()).collect();
+        let mut nodes: Vec<_> = (0..points.len()).collect();
+        let mut sizes = vec![1; points.len()];
+        let mut merges = Vec::with_capacity(points.len().saturating_sub(1));
+
+        for (connection, (i, j, distance)) in distances.into_iter().enumerate() {
+            if merges.len() + 1 >= points.len() {
+                break;
+            }
+
+            let p = connections.find(i);
+            let q = connections.find(j);
+
+            if p == q {
+                continue;
+            }
+
+            connections.union(p, q);
+
+            let size = sizes[p] + sizes[q];
+            let (left, right) = (nodes[p], nodes[q]);
+
+            sizes[p] = size;
+            nodes[p] = points.len() + merges.len();
+            merges.push(Merge {
+                connection,
+                a: i,
+                b: j,
+                distance,
+                size,
+                left,
+                right,
+            });
+        }
+
+        Dendrogram {
+            points: points.len(),
+            merges,
+        }
+    }
+
+    pub fn merges(&self) -> &[Merge] {
+        &self.merges
+    }
+
+    pub fn last_merge(&self) -> Option<&Merge> {
+        self.merges.last()
+    }
+
+    /// Circuit sizes, largest first, after the closest `connections` pairs have been connected.
+    pub fn sizes_after(&self, connections: usize) -> Vec<usize> {
+        let mut circuits: Vec<_> = (0..self.points).collect();
+
+        for merge in self.merges.iter().take_while(|merge| merge.connection < connections) {
+            circuits.union(merge.a, merge.b);
+        }
+
+        let mut counts = vec![0; self.points];
+
+        for i in 0..self.points {
+            counts[circuits.find(i)] += 1;
+        }
+
+        counts.retain(|count| *count > 0);
+        counts.sort_unstable_by(|a, b| b.cmp(a));
+        counts
+    }
+
+    /// Squared distance of the merge that leaves exactly `circuits` circuits, if there is one.
+    pub fn threshold(&self, circuits: usize) -> Option<u64> {
+        if circuits == 0 || circuits >= self.points {
+            return None;
+        }
+
+        self.merges.get(self.points - circuits - 1).map(|merge| merge.distance)
+    }
+
+    fn height(&self, node: usize) -> f64 {
+        if node < self.points {
+            0.0
+        } else {
+            (self.merges[node - self.points].distance as f64).sqrt()
+        }
+    }
+
+    fn write_newick(&self, node: usize, out: &mut String) {
+        if node < self.points {
+            out.push_str(&node.to_string());
+            return;
+        }
+
+        let merge = &self.merges[node - self.points];
+        let height = self.height(node);
+
+        out.push('(');
+
+        for (i, child) in [merge.left, merge.right].into_iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+
+            self.write_newick(child, out);
+            out.push_str(&format!(":{}", height - self.height(child)));
+        }
+
+        out.push(')');
+    }
+
+    /// Newick tree with point indices as leaf labels and euclidean distances as branch lengths.
+    pub fn to_newick(&self) -> String {
+        let mut merged = vec![false; self.points + self.merges.len()];
+
+        for merge in &self.merges {
+            merged[merge.left] = true;
+            merged[merge.right] = true;
+        }
+
+        let roots: Vec<usize> = (0..merged.len()).filter(|node| !merged[*node]).collect();
+        let mut out = String::new();
+
+        if roots.len() > 1 {
+            out.push('(');
+        }
+
+        for (i, root) in roots.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+
+            self.write_newick(*root, &mut out);
+        }
+
+        if roots.len() > 1 {
+            out.push(')');
+        }
+
+        out.push(';');
+        out
+    }
+
+    pub fn to_json(&self) -> String {
+        let merges = self
+            .merges
+            .iter()
+            .map(|merge| {
+                format!(
+                    r#"{{"connection":{},"a":{},"b":{},"distance":{},"size":{},"left":{},"right":{}}}"#,
+                    merge.connection, merge.a, merge.b, merge.distance, merge.size, merge.left, merge.right
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+
+        format!(r#"{{"points":{},"merges":[{merges}]}}"#, self.points)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+162,817,812
+57,618,57
+906,360,560
+592,479,940
+352,342,300
+466,668,158
+542,29,236
+431,825,988
+739,650,466
+52,470,668
+216,146,977
+819,987,18
+117,168,530
+805,96,715
+346,949,466
+970,615,88
+941,993,340
+862,61,35
+984,92,344
+425,690,689
+";
+
+    fn dendrogram(s: &str) -> (Vec<Point>, Dendrogram) {
+        let points: Vec<Point> = parse::records(s, Mode::Strict).unwrap();
+        let dendrogram = Dendrogram::build(&points);
+
+        (points, dendrogram)
+    }
+
+    /// Three points on a line, 3 and then 7 apart.
+    fn line() -> Dendrogram {
+        dendrogram("0,0,0\n3,0,0\n10,0,0\n").1
+    }
+
+    #[test]
+    fn solves_the_example() {
+        let (points, dendrogram) = dendrogram(EXAMPLE);
+        let sizes = dendrogram.sizes_after(10);
+
+        assert_eq!(sizes[..3], [5, 4, 2]);
+        assert_eq!(sizes.iter().sum::<usize>(), 20);
+
+        let last = dendrogram.last_merge().unwrap();
+
+        assert_eq!(points[last.a].x * points[last.b].x, 25272);
+        assert_eq!((dendrogram.merges().len(), last.size), (19, 20));
+    }
+
+    #[test]
+    fn counts_circuits_after_each_connection() {
+        let line = line();
+
+        assert_eq!(line.sizes_after(0), [1, 1, 1]);
+        assert_eq!(line.sizes_after(1), [2, 1]);
+        assert_eq!(line.sizes_after(2), [3]);
+        assert_eq!(line.sizes_after(3), [3]);
+    }
+
+    #[test]
+    fn finds_the_distance_leaving_some_circuits() {
+        let line = line();
+
+        assert_eq!([0, 1, 2, 3].map(|circuits| line.threshold(circuits)), [None, Some(49), Some(9), None]);
+    }
+
+    #[test]
+    fn writes_the_dendrogram() {
+        let line = line();
+
+        assert_eq!(line.to_newick(), "((0:3,1:3):4,2:7);");
+        assert_eq!(
+            line.to_json(),
+            concat!(
+                r#"{"points":3,"merges":["#,
+                r#"{"connection":0,"a":0,"b":1,"distance":9,"size":2,"left":0,"right":1},"#,
+                r#"{"connection":1,"a":1,"b":2,"distance":49,"size":3,"left":3,"right":2}]}"#
+            )
+        );
+        assert_eq!(dendrogram("").1.to_newick(), ";");
+        assert_eq!(dendrogram("1,2,3\n").1.to_newick(), "0;");
+        assert_eq!(dendrogram("1,2,3\n").1.to_json(), r#"{"points":1,"merges":[]}"#);
+    }
+
+    #[test]
+    fn rejects_malformed_points() {
+        assert!(matches!("1,2".parse::<Point>(), Err(ParsePointError::WrongFieldCount)));
+        assert!(matches!("1,x,3".parse::<Point>(), Err(ParsePointError::InvalidNumber(_))));
+        assert_eq!(parse::records::<Point>("1,2,3\n1,2\n", Mode::Lenient).unwrap().len(), 1);
+    }
+}
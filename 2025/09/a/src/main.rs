@@ -1,13 +1,18 @@
 use std::{
     error,
     fmt::{self, Display, Formatter},
-    fs::File,
-    io::{self, BufRead, BufReader},
+    io,
     num::ParseIntError,
     path::Path,
     str::FromStr,
 };
 
+use aoc::{
+    input,
+    parse::{self, Mode},
+};
+
+#[derive(Clone, PartialEq, Eq, Hash)]
 struct Point {
     x: u64,
     y: u64,
@@ -60,22 +65,12 @@ impl FromStr for Point {
     }
 }
 
-fn read_points(path: &Path) -> io::Result<Vec<Point>> {
-    let file = File::open(path)?;
-    let reader = BufReader::new(file);
-
-    reader
-        .lines()
-        .map(|line| {
-            line?
-                .parse::<Point>()
-                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
-        })
-        .collect::<Result<Vec<Point>, _>>()
+fn read_points(path: &Path, mode: Mode) -> io::Result<Vec<Point>> {
+    parse::records(&input::read(path)?, mode)
 }
 
 fn main() -> io::Result<()> {
-    let mut points = read_points(Path::new("../input"))?;
+    let mut points = read_points(Path::new("../input"), Mode::from_args())?;
     points.sort_by_key(|p| (p.y, p.x));
     let points = &points;
 
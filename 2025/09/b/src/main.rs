@@ -1,14 +1,22 @@
+mod verify;
+
 use std::{
     cmp::Ordering,
+    env,
     error,
     fmt::{self, Display, Formatter},
-    fs::File,
-    io::{self, BufRead, BufReader},
+    io,
     num::ParseIntError,
     path::Path,
     str::FromStr,
 };
 
+use aoc::{
+    input,
+    parse::{self, Mode},
+};
+
+#[derive(Clone, PartialEq, Eq, Hash)]
 struct Point {
     x: u64,
     y: u64,
@@ -61,18 +69,30 @@ impl FromStr for Point {
     }
 }
 
-fn read_points(path: &Path) -> io::Result<Vec<Point>> {
-    let file = File::open(path)?;
-    let reader = BufReader::new(file);
+fn read_points(path: &Path, mode: Mode) -> io::Result<Vec<Point>> {
+    parse::records(&input::read(path)?, mode)
+}
+
+/// Whether the loop runs clockwise on screen, with y growing downwards, as in the puzzle's example: twice its signed
+/// area, by the shoelace formula, is then positive.
+fn is_clockwise(points: &[Point]) -> bool {
+    let twice_area: i128 = (0..points.len())
+        .map(|i| {
+            let (p, q) = (&points[i], &points[(i + 1) % points.len()]);
 
-    reader
-        .lines()
-        .map(|line| {
-            line?
-                .parse::<Point>()
-                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+            i128::from(p.x) * i128::from(q.y) - i128::from(q.x) * i128::from(p.y)
         })
-        .collect::<Result<Vec<Point>, _>>()
+        .sum();
+
+    twice_area > 0
+}
+
+/// Reverses the loop when it runs counterclockwise: the border tests tell the inside of the loop by the direction of
+/// its edges, which assumes it runs clockwise.
+fn make_clockwise(points: &mut [Point]) {
+    if !is_clockwise(points) {
+        points.reverse();
+    }
 }
 
 struct Edge<'a> {
@@ -275,11 +295,11 @@ impl<'a> Solver<'a> {
         false
     }
 
-    fn solve(&self) -> u64 {
+    fn solve(&self) -> Option<Rectangle> {
         (0..self.points.len().saturating_sub(1))
             .flat_map(|i| (i + 1..self.points.len()).map(move |j| (i, j)))
             .filter(|v| v.0 != v.1)
-            .map(|(i, j)| {
+            .filter_map(|(i, j)| {
                 let p = &self.points[i];
                 let q = &self.points[j];
 
@@ -293,7 +313,7 @@ impl<'a> Solver<'a> {
                 let area = dx * dy;
 
                 if self.horizontal_cross(x0, x1, y0, y1) || self.vertical_cross(x0, x1, y0, y1) {
-                    return 0;
+                    return None;
                 }
 
                 let top_conflict =
@@ -306,20 +326,75 @@ impl<'a> Solver<'a> {
                     self.vertical_border_conflict(&self.up_edges, y0, y1, x1);
 
                 if top_conflict || bottom_conflict || left_conflict || right_conflict {
-                    return 0;
+                    return None;
                 }
 
-                area
+                Some(Rectangle { a: i, b: j, area })
             })
-            .max()
-            .unwrap()
+            .max_by_key(|rectangle| rectangle.area)
     }
 }
 
+/// The largest rectangle found, spanned by two red tiles given as indices into the input.
+struct Rectangle {
+    a: usize,
+    b: usize,
+    area: u64,
+}
+
 fn main() -> io::Result<()> {
-    let points = read_points(Path::new("../input"))?;
+    let mut points = read_points(Path::new("../input"), Mode::from_args())?;
+
+    make_clockwise(&mut points);
+
     let solver = Solver::new(&points);
-    let area = solver.solve();
-    println!("{area}");
+    let Some(rectangle) = solver.solve() else {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "No rectangle fits inside the loop"));
+    };
+
+    if env::args().any(|arg| arg == "--witness") {
+        println!("{} {}", points[rectangle.a], points[rectangle.b]);
+    }
+
+    if env::args().any(|arg| arg == "--verify") {
+        let area = verify::rectangle(&points, &points[rectangle.a], &points[rectangle.b]).map_err(io::Error::other)?;
+
+        if area != rectangle.area {
+            return Err(io::Error::other(format!("solver reported {}, witness gives {area}", rectangle.area)));
+        }
+
+        eprintln!("verified");
+    }
+
+    println!("{}", rectangle.area);
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "7,1\n11,1\n11,7\n9,7\n9,5\n2,5\n2,3\n7,3\n";
+
+    fn largest(mut points: Vec<Point>) -> Option<u64> {
+        make_clockwise(&mut points);
+        Solver::new(&points).solve().map(|rectangle| rectangle.area)
+    }
+
+    #[test]
+    fn solves_the_example() {
+        let points: Vec<Point> = EXAMPLE.lines().map(|line| line.parse().unwrap()).collect();
+
+        assert!(is_clockwise(&points));
+        assert_eq!(largest(points), Some(24));
+    }
+
+    /// The differential check found the solver rejecting this square, which runs counterclockwise.
+    #[test]
+    fn solves_loops_running_counterclockwise() {
+        let points: Vec<Point> = ["0,6", "2,6", "2,4", "0,4"].iter().map(|line| line.parse().unwrap()).collect();
+
+        assert!(!is_clockwise(&points));
+        assert_eq!(largest(points), Some(9));
+    }
+}
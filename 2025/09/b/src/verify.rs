@@ -0,0 +1,89 @@
+//! Checks a rectangle against the loop without the solver's edge tests, by flood filling the outside of the loop on
+//! a compressed grid.
+
+use std::collections::VecDeque;
+
+use crate::Point;
+
+/// Maps coordinates to compressed indices: every distinct value gets an odd index, the gaps around them even ones.
+struct Axis(Vec<u64>);
+
+impl Axis {
+    fn new(values: impl Iterator<Item = u64>) -> Self {
+        let mut values: Vec<u64> = values.collect();
+
+        values.sort_unstable();
+        values.dedup();
+
+        Axis(values)
+    }
+
+    fn index(&self, value: u64) -> Option<usize> {
+        self.0.binary_search(&value).ok().map(|i| 2 * i + 1)
+    }
+
+    fn len(&self) -> usize {
+        2 * self.0.len() + 1
+    }
+}
+
+/// Area of the rectangle with opposite corners `a` and `b`, if both are red tiles and every tile of the rectangle is
+/// red or green.
+pub fn rectangle(points: &[Point], a: &Point, b: &Point) -> Result<u64, String> {
+    for corner in [a, b] {
+        if !points.contains(corner) {
+            return Err(format!("corner {corner} is not a red tile"));
+        }
+    }
+
+    let xs = Axis::new(points.iter().map(|p| p.x));
+    let ys = Axis::new(points.iter().map(|p| p.y));
+    let (width, height) = (xs.len(), ys.len());
+    let mut border = vec![false; width * height];
+
+    for (i, p) in points.iter().enumerate() {
+        let q = &points[(i + 1) % points.len()];
+
+        if p.x != q.x && p.y != q.y {
+            return Err(format!("{p} and {q} are not on a common row or column"));
+        }
+
+        let (px, py) = (xs.index(p.x).unwrap(), ys.index(p.y).unwrap());
+        let (qx, qy) = (xs.index(q.x).unwrap(), ys.index(q.y).unwrap());
+
+        for x in px.min(qx)..=px.max(qx) {
+            for y in py.min(qy)..=py.max(qy) {
+                border[y * width + x] = true;
+            }
+        }
+    }
+
+    let mut outside = vec![false; width * height];
+    let mut queue = VecDeque::from([(0_usize, 0_usize)]);
+
+    outside[0] = true;
+
+    while let Some((x, y)) = queue.pop_front() {
+        let neighbours = [(x.wrapping_sub(1), y), (x + 1, y), (x, y.wrapping_sub(1)), (x, y + 1)];
+
+        for (nx, ny) in neighbours {
+            if nx < width && ny < height && !border[ny * width + nx] && !outside[ny * width + nx] {
+                outside[ny * width + nx] = true;
+                queue.push_back((nx, ny));
+            }
+        }
+    }
+
+    let (ax, ay) = (xs.index(a.x).unwrap(), ys.index(a.y).unwrap());
+    let (bx, by) = (xs.index(b.x).unwrap(), ys.index(b.y).unwrap());
+
+    for x in ax.min(bx)..=ax.max(bx) {
+        for y in ay.min(by)..=ay.max(by) {
+            if outside[y * width + x] {
+                return Err(format!("rectangle {a} {b} leaves the loop"));
+            }
+        }
+    }
+
+    Ok((a.x.abs_diff(b.x) + 1) * (a.y.abs_diff(b.y) + 1))
+}
@@ -0,0 +1,265 @@
+use std::{
+    collections::{HashMap, hash_map::Entry},
+    io,
+    mem,
+};
+
+use machine::{Lights, Machine};
+
+/// Most redundant buttons whose combinations are enumerated rather than searched.
+const MAX_ENUMERATED: usize = 24;
+/// Most light states the search keeps, between both of its sides.
+const MAX_STATES: usize = 1 << 24;
+
+/// A combination of buttons reduced during elimination, with the buttons pressed to make it up.
+struct Row {
+    lights: Lights,
+    presses: Lights,
+    pivot: usize,
+}
+
+/// Index of the lowest set bit.
+fn lowest(bits: &Lights) -> Option<usize> {
+    let (i, word) = bits.words().iter().enumerate().find(|(_, word)| **word != 0)?;
+
+    Some(i * 64 + word.trailing_zeros() as usize)
+}
+
+fn count(bits: &Lights) -> usize {
+    bits.words().iter().map(|word| word.count_ones() as usize).sum()
+}
+
+/// Clears the pivot of every row from `lights`, recording the buttons that took in `presses`.
+fn reduce(rows: &[Row], lights: &mut Lights, presses: &mut Lights) {
+    for row in rows {
+        if lights.get(row.pivot) {
+            lights.xor(&row.lights);
+            presses.xor(&row.presses);
+        }
+    }
+}
+
+/// The smallest of `presses` combined with any set of the `null_space` vectors, trying all of them in Gray code
+/// order so each combination differs from the last by one vector.
+fn enumerate(mut presses: Lights, null_space: &[Lights]) -> Lights {
+    let mut best = presses.clone();
+
+    for step in 1..1_u64 << null_space.len() {
+        presses.xor(&null_space[step.trailing_zeros() as usize]);
+
+        if count(&presses) < count(&best) {
+            best = presses.clone();
+        }
+    }
+
+    best
+}
+
+/// Light states reached from one end of the search, each with the state before it and the button leading from there.
+type Reached = HashMap<Lights, Option<(Lights, usize)>>;
+
+/// Buttons pressed on the way from the end `reached` started from to `state`.
+fn path<'a>(reached: &'a Reached, mut state: &'a Lights) -> Vec<usize> {
+    let mut buttons = vec![];
+
+    while let Some(Some((previous, button))) = reached.get(state) {
+        buttons.push(*button);
+        state = previous;
+    }
+
+    buttons
+}
+
+/// Breadth-first search from both the lights off and the target, a layer of the smaller side at a time, until the
+/// two meet. Pressing a button is its own inverse, so the search from the target presses the same buttons; the states
+/// are those of the span of the buttons, 2^rank of them at most, however many buttons there are.
+fn meet_in_the_middle(masks: &[Lights], target: &Lights) -> io::Result<Vec<usize>> {
+    let off = Lights::new(target.len());
+
+    if *target == off {
+        return Ok(vec![]);
+    }
+
+    let mut sides = [Reached::from([(off.clone(), None)]), Reached::from([(target.clone(), None)])];
+    let mut frontiers = [vec![off], vec![target.clone()]];
+
+    loop {
+        let side = if frontiers[0].len() <= frontiers[1].len() { 0 } else { 1 };
+        let [from_off, from_target] = &mut sides;
+        let (reached, other) = if side == 0 { (from_off, &*from_target) } else { (from_target, &*from_off) };
+        let mut next = vec![];
+        let mut meeting: Option<(usize, Lights)> = None;
+
+        for state in mem::take(&mut frontiers[side]) {
+            for (button, mask) in masks.iter().enumerate() {
+                let mut pressed = state.clone();
+
+                pressed.xor(mask);
+
+                if let Entry::Vacant(entry) = reached.entry(pressed.clone()) {
+                    entry.insert(Some((state.clone(), button)));
+
+                    // The layer is finished before stopping, as a later state may meet the other side sooner
+                    if other.contains_key(&pressed) {
+                        let length = path(other, &pressed).len();
+
+                        if meeting.as_ref().is_none_or(|(best, _)| length < *best) {
+                            meeting = Some((length, pressed.clone()));
+                        }
+                    }
+
+                    next.push(pressed);
+                }
+            }
+        }
+
+        if let Some((_, state)) = meeting {
+            let mut buttons = path(&sides[0], &state);
+
+            buttons.extend(path(&sides[1], &state));
+            buttons.sort_unstable();
+            return Ok(buttons);
+        }
+
+        if sides[0].len() + sides[1].len() > MAX_STATES {
+            return Err(io::Error::other(format!("more than {MAX_STATES} light states to search")));
+        }
+
+        frontiers[side] = next;
+    }
+}
+
+/// Returns the buttons of a minimal press set reaching the machine's indicator target, or `None` when no set does.
+///
+/// Pressing a button twice cancels out, so a solution is a set of buttons whose masks XOR to the target, that is a
+/// solution of a linear system over GF(2). Gaussian elimination tells whether there is one and splits the buttons
+/// into `rank` independent ones and redundant ones. When few are redundant every solution is tried: they are one
+/// solution plus any combination of the null space. Otherwise the lights reachable are few, 2^rank, and a search
+/// over them from both ends finds the fewest presses however many buttons there are.
+pub fn min_presses(machine: &Machine) -> io::Result<Option<Vec<usize>>> {
+    let buttons = machine.buttons().len();
+    let masks: Vec<Lights> = (0..buttons).map(|i| machine.button_mask(i)).collect();
+    let mut rows: Vec<Row> = vec![];
+    let mut null_space: Vec<Lights> = vec![];
+
+    for (i, mask) in masks.iter().enumerate() {
+        let mut lights = mask.clone();
+        let mut presses = Lights::new(buttons);
+
+        presses.toggle(i);
+        reduce(&rows, &mut lights, &mut presses);
+
+        // Rows clear the pivots of the earlier rows, so a new pivot is never one of theirs
+        match lowest(&lights) {
+            Some(pivot) => rows.push(Row { lights, presses, pivot }),
+            None => null_space.push(presses),
+        }
+    }
+
+    let mut lights = machine.indicators().clone();
+    let mut presses = Lights::new(buttons);
+
+    reduce(&rows, &mut lights, &mut presses);
+
+    if lowest(&lights).is_some() {
+        return Ok(None);
+    }
+
+    if null_space.len() > MAX_ENUMERATED {
+        return meet_in_the_middle(&masks, machine.indicators()).map(Some);
+    }
+
+    let best = enumerate(presses, &null_space);
+
+    Ok(Some((0..buttons).filter(|&i| best.get(i)).collect()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use aoc::rng::Rng;
+
+    use super::*;
+
+    fn presses(line: &str) -> Option<Vec<usize>> {
+        min_presses(&line.parse().unwrap()).unwrap()
+    }
+
+    fn machine(lights: &str, buttons: &[&str]) -> String {
+        let joltages = vec!["0"; lights.len()].join(",");
+
+        format!("[{lights}] {} {{{joltages}}}", buttons.join(" "))
+    }
+
+    #[test]
+    fn solves_the_example() {
+        let counts: Vec<usize> = [
+            "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}",
+            "[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}",
+            "[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}",
+        ]
+        .iter()
+        .map(|line| presses(line).unwrap().len())
+        .collect();
+
+        assert_eq!(counts, [2, 3, 2]);
+    }
+
+    #[test]
+    fn finds_the_buttons_pressed() {
+        assert_eq!(presses("[##.] (0) (1) (0,1) (2) {0,0,0}"), Some(vec![2]));
+        assert_eq!(presses("[...] (0) (1) {0,0,0}"), Some(vec![]));
+        assert_eq!(presses("[..#] (0) (1) {0,0,0}"), None);
+    }
+
+    #[test]
+    fn search_agrees_with_trying_every_set() {
+        let mut rng = Rng::new(1);
+
+        for _ in 0..200 {
+            let width = rng.between(1, 8) as usize;
+            let masks: Vec<Lights> = (0..rng.between(1, 12))
+                .map(|_| {
+                    let mut mask = Lights::new(width);
+
+                    (0..width).filter(|_| rng.chance(0.4)).for_each(|i| mask.toggle(i));
+                    mask
+                })
+                .collect();
+            let mut target = Lights::new(width);
+
+            // A target every machine reaches, unlike a random one
+            masks.iter().filter(|_| rng.chance(0.5)).for_each(|mask| target.xor(mask));
+
+            let fewest = (0..1_usize << masks.len())
+                .filter(|set| {
+                    let mut lights = Lights::new(width);
+
+                    (0..masks.len()).filter(|i| set >> i & 1 == 1).for_each(|i| lights.xor(&masks[i]));
+                    lights == target
+                })
+                .map(|set| set.count_ones() as usize)
+                .min();
+
+            assert_eq!(meet_in_the_middle(&masks, &target).ok().map(|p| p.len()), fewest);
+        }
+    }
+
+    #[test]
+    fn searches_machines_with_many_redundant_buttons() {
+        assert_eq!(presses(&machine("#", &["(0)"; 70])).map(|p| p.len()), Some(1));
+
+        let buttons = [vec!["(0)"; 34], vec!["(1)"]].concat();
+
+        assert_eq!(presses(&machine("#.", &buttons)).map(|p| p.len()), Some(1));
+
+        // Each light on four buttons of its own, a search twelve presses deep
+        let buttons: Vec<String> = (0..48).map(|i| format!("({})", i % 12)).collect();
+        let buttons: Vec<&str> = buttons.iter().map(String::as_str).collect();
+        let pressed = presses(&machine(&"#".repeat(12), &buttons)).unwrap();
+
+        assert_eq!(pressed.len(), 12);
+        assert!(pressed.iter().map(|i| i % 12).collect::<BTreeSet<_>>().len() == 12, "every light is toggled");
+    }
+}
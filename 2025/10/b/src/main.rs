@@ -1,40 +1,30 @@
+mod verify;
+
 use std::{
-    collections::HashMap, error, fmt::{self, Display, Formatter}, fs::File, io::{self, BufRead, BufReader}, iter, num::ParseIntError, path::Path, str::FromStr
+    env,
+    fmt::{self, Display, Formatter},
+    io,
+    iter,
+    path::Path,
+};
+
+use aoc::{
+    budget::{Budget, Meter, Outcome},
+    debug,
+    memo::{self, Key, KeyBuilder, Memo},
+    parallel::Pool,
+    parse::Mode,
+    trace,
 };
+use machine::{Machine, read_machines};
 
-use regex::Regex;
+const TARGET: &str = "day10";
 
 #[derive(Clone)]
 struct State {
     joltages: Vec<u32>,
 }
 
-impl State {
-    fn new(joltages: Vec<u32>) -> Self {
-        State { joltages }
-    }
-
-    fn add(&self, rhs: &Self) -> Self {
-        let mut joltages = vec![0; self.joltages.len()];
-
-        for i in 0..self.joltages.len() {
-            joltages[i] = self.joltages[i] + rhs.joltages[i];
-        }
-
-        Self { joltages }
-    }
-
-    fn mul(&self, rhs: u32) -> Self {
-        let mut joltages = vec![0; self.joltages.len()];
-
-        for i in 0..self.joltages.len() {
-            joltages[i] = self.joltages[i] * rhs;
-        }
-
-        Self { joltages }
-    }
-}
-
 impl PartialEq for State {
     fn eq(&self, other: &Self) -> bool {
         self.joltages == other.joltages
@@ -53,13 +43,6 @@ impl Display for State {
     }
 }
 
-struct Machine {
-    target: State,
-    buttons: Vec<State>,
-    width: usize,
-    indicators: u64,
-}
-
 struct ButtonsFmt<'a>(&'a [State]);
 
 impl<'a> Display for ButtonsFmt<'a> {
@@ -74,8 +57,8 @@ impl<'a> Display for ButtonsFmt<'a> {
             write!(f, "(")?;
             let mut first = true;
 
-            for j in 0..joltages.len() {
-                if joltages[j] == 1 {
+            for (j, &joltage) in joltages.iter().enumerate() {
+                if joltage == 1 {
                     if !first {
                         write!(f, ",")?;
                     }
@@ -92,107 +75,16 @@ impl<'a> Display for ButtonsFmt<'a> {
     }
 }
 
-fn fmt_indicators(indicators: u64, width: usize) -> String {
-    format!("[{:0width$b}]", indicators, width = width)
-        .replace('0', ".")
-        .replace('1', "#")
-}
-
-impl fmt::Display for Machine {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{} ", fmt_indicators(self.indicators, self.width))?;
-
-        ButtonsFmt(&self.buttons).fmt(f)?;
-
-        self.target.fmt(f)
-    }
-}
-
-#[derive(Debug)]
-enum ParseMachineError {
-    InvalidSyntax,
-    InvalidNumber(ParseIntError),
-}
-
-impl Display for ParseMachineError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        match self {
-            Self::InvalidSyntax => write!(f, "Invalid syntax"),
-            Self::InvalidNumber(e) => write!(f, "Invalid number: {e}"),
-        }
-    }
-}
-
-impl error::Error for ParseMachineError {}
-
-impl FromStr for Machine {
-    type Err = ParseMachineError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let outer_re = Regex::new(r"\[(?<target>[\.#]{1,64})\] (?<buttons>.+?) (?P<joltages>\{([\d,]+)\})").unwrap();
-        let inner_re = Regex::new(r"\((?<indices>[\d,]+)\)").unwrap();
-
-        let captures = outer_re.captures(s).ok_or(ParseMachineError::InvalidSyntax)?;
-
-        let target = &captures["target"];
-        let width = target.len();
-        let target = target
-            .chars()
-            .fold(0u64, |acc, c| (acc << 1) | (if c == '#' { 1 } else { 0 }));
-
-        let buttons: Result<Vec<State>, _> = inner_re
-            .captures_iter(&captures["buttons"])
-            .map(|captures| {
-                let mut joltages = vec![0_u32; width];
-
-                captures["indices"].split(',').try_for_each(|number| {
-                    let index = number.parse::<usize>().map_err(ParseMachineError::InvalidNumber)?;
-
-                    joltages[index] = 1;
-                    Ok(())
-                })?;
-
-                Ok(State::new(joltages))
-            })
-            .collect();
-
-        let joltages: Vec<u32> = captures["joltages"]
-            .trim_matches(|c| c == '{' || c == '}')
-            .split(',')
-            .map(|number| number.parse::<u32>().map_err(ParseMachineError::InvalidNumber))
-            .collect::<Result<_, _>>()?;
-
-        Ok(Machine {
-            indicators: target,
-            width,
-            buttons: buttons?,
-            target: State::new(joltages),
-        })
-    }
-}
-
-fn read_machines(path: &Path) -> io::Result<Vec<Machine>> {
-    let file = File::open(path)?;
-    let reader = BufReader::new(file);
-
-    reader
-        .lines()
-        .map(|line| {
-            line?
-                .parse::<Machine>()
-                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
-        })
-        .collect::<Result<Vec<Machine>, _>>()
-}
-
 enum Stage {
     Initial,
     Continuation,
     Final,
 }
 
+type Frame = (usize, u32, Box<dyn Iterator<Item = u32>>, Stage);
+
 struct Permutations {
-    stack: Vec<(usize, u32, Box<dyn Iterator<Item = u32>>, Stage)>,
+    stack: Vec<Frame>,
     current: Vec<u32>,
     maxes: Vec<u32>,
 }
@@ -252,96 +144,179 @@ impl Iterator for Permutations {
     }
 }
 
-fn main() -> io::Result<()> {
-    let machines = read_machines(Path::new("../input"))?;
+struct Report {
+    /// Fewest presses, or the best count found so far when the budget ran out.
+    outcome: Outcome<Option<u32>>,
+    /// Presses of each of the machine's buttons, once the search finished.
+    presses: Option<Vec<u32>>,
+}
+
+/// Fewest presses reaching the machine's joltages.
+fn solve(machine: &Machine, budget: &Budget, capacity: Option<usize>) -> Report {
+    debug!(TARGET, "{machine}");
     let mut sum = 0;
 
-    for machine in &machines {
-        println!("2. {machine}");
+    let rhs = machine.joltages().to_vec();
+    let coefficients = (0..machine.buttons().len())
+        .map(|i| machine.button_vector(i))
+        .collect::<Vec<_>>();
 
-        let rhs = machine.target.joltages.clone();
-        let coefficients = machine
-            .buttons
-            .iter()
-            .map(|button| button.joltages.to_owned())
-            .collect::<Vec<_>>();
+    let (coefficients, rhs) = build_system_from_buttons(coefficients, rhs);
+    let (coefficients, rhs) = simplify(coefficients, rhs);
+    let (coefficients, rhs, trivial) = extract_trivial_solutions(coefficients, rhs);
+    let (coefficients, rhs, columns) = buttons_from_system(coefficients, rhs);
 
-        let (coefficients, rhs) = build_system_from_buttons(coefficients, rhs);
-        let (coefficients, rhs) = simplify(coefficients, rhs);
-        let (coefficients, rhs, count) = extract_trivial_solutions(coefficients, rhs);
-        let (coefficients, rhs) = buttons_from_system(coefficients, rhs);
+    sum += trivial.iter().map(|(_, count)| count).sum::<u32>();
 
-        sum += count;
+    // ---- here was the mess before ----
 
-        // ---- here was the mess before ----
+    // Keep a copy of rhs as Vec<u32> for Solver
+    let target_vec = rhs.clone();
+    // Keep a copy of coefficients as Vec<Vec<u32>> for Solver
+    let buttons_vec = coefficients.clone();
 
-        // Keep a copy of rhs as Vec<u32> for Solver
-        let target_vec = rhs.clone();
-        // Keep a copy of coefficients as Vec<Vec<u32>> for Solver
-        let buttons_vec = coefficients.clone();
+    // State versions for printing and for `current`
+    let target = State { joltages: rhs };
+    let buttons_states = buttons_vec
+        .iter()
+        .cloned()
+        .map(|joltages| State { joltages })
+        .collect::<Vec<_>>();
 
-        // State versions for printing and for `current`
-        let target = State { joltages: rhs };
-        let buttons_states = buttons_vec
-            .iter()
-            .cloned()
-            .map(|joltages| State { joltages })
-            .collect::<Vec<_>>();
+    debug!(TARGET, "reduced buttons {}", ButtonsFmt(&buttons_states));
+    debug!(TARGET, "reduced target{target}");
+
+    // build solver on raw vectors
+    let solver = Solver::new(target_vec, buttons_vec);
+
+    let mut meter = budget.start();
+    let mut memo = Memo::with_capacity(capacity);
+    let (count, solver_presses) = solver.solve(&mut meter, &mut memo);
+
+    debug!(TARGET, "memo {}", memo.stats());
+
+    if let Some(count) = count {
+        debug!(TARGET, "{count} presses after {sum} fixed ones");
+    }
+
+    let presses = solver_presses.map(|solver_presses| {
+        let mut presses = vec![0; machine.buttons().len()];
+
+        for (button, count) in trivial {
+            presses[button] += count;
+        }
+
+        for (j, count) in solver_presses.into_iter().enumerate() {
+            presses[columns[j]] += count;
+        }
+
+        presses
+    });
+
+    let outcome = meter.finish(count.map(|count| sum + count));
+
+    debug!(TARGET, "searched {}", outcome.stats);
+
+    Report { outcome, presses }
+}
+
+fn main() -> io::Result<()> {
+    trace::init()?;
+
+    let machines = read_machines(Path::new("../input"), Mode::from_args())?;
+    let budget = Budget::from_args()?;
+    let capacity = memo::capacity_from_args()?;
+    let witness = env::args().any(|arg| arg == "--witness");
+    let verify = env::args().any(|arg| arg == "--verify");
+    let records: Vec<_> = machines.iter().enumerate().collect();
+    let solved = Pool::from_args()?.map(&records, |&(i, machine)| {
+        trace::span(format!("machine {}", i + 1), || solve(machine, &budget, capacity))
+    });
+    let mut sum = 0;
+    let mut unfinished = 0;
+
+    for (i, report) in solved.results.iter().enumerate() {
+        let outcome = &report.outcome;
+
+        match (outcome.exceeded, outcome.value) {
+            (None, Some(count)) => sum += count,
+            (None, None) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("No button presses reach the joltages of {}", machines[i]),
+                ));
+            }
+            (Some(exceeded), best) => {
+                let best = best.map_or("none".to_owned(), |count| count.to_string());
+
+                eprintln!("machine {}: {exceeded} exceeded after {}, best so far {best}", i + 1, outcome.stats);
+                unfinished += 1;
+                continue;
+            }
+        }
+
+        let Some(presses) = &report.presses else {
+            continue;
+        };
+
+        if witness {
+            let presses = presses.iter().map(|n| n.to_string()).collect::<Vec<_>>().join(",");
 
-        println!("3. {}", ButtonsFmt(&buttons_states));
-        println!("4. {}", target);
+            println!("{}: presses {presses}", machines[i]);
+        }
 
-        let len = target.joltages.len();
+        if verify {
+            let count = verify::presses(&machines[i], presses).map_err(io::Error::other)?;
 
-        // build solver on raw vectors
-        let solver = Solver::new(target_vec, buttons_vec);
+            if Some(count) != outcome.value {
+                return Err(io::Error::other(format!(
+                    "machine {}: solver reported {}, witness gives {count}",
+                    i + 1,
+                    outcome.value.unwrap_or_default()
+                )));
+            }
+        }
+    }
 
-        // button ids: 0..N
-        let button_ids: Vec<usize> = (0..solver.buttons.len()).collect();
+    solved.report();
 
-        let current = vec![0_u32; solver.len];
-        let count = solver.solve().unwrap();
+    if unfinished > 0 {
+        return Err(io::Error::other(format!("{unfinished} machines unfinished")));
+    }
 
-        println!("5. count = {count}");
-        sum += count;
+    if verify {
+        eprintln!("verified");
     }
 
-    println!("6. {sum}");
+    println!("{sum}");
 
     Ok(())
 }
 
-fn buttons_from_system(coefficients: Vec<Vec<u32>>, rhs: Vec<u32>) -> (Vec<Vec<u32>>, Vec<u32>) {
-    let rows = coefficients.len();
-    if rows == 0 {
-        return (Vec::new(), rhs);
+/// Turns the system back into buttons, skipping columns that became all zeros. Also returns the column each button
+/// came from.
+fn buttons_from_system(coefficients: Vec<Vec<u32>>, rhs: Vec<u32>) -> (Vec<Vec<u32>>, Vec<u32>, Vec<usize>) {
+    if coefficients.is_empty() {
+        return (Vec::new(), rhs, Vec::new());
     }
 
     let cols = coefficients[0].len();
     let mut buttons = Vec::new();
+    let mut columns = Vec::new();
 
     for k in 0..cols {
-        // check if this column has any non-zero entries
-        let mut non_zero = false;
-        for p in 0..rows {
-            if coefficients[p][k] != 0 {
-                non_zero = true;
-                break;
-            }
-        }
-        if !non_zero {
+        // build the button from this column
+        let button: Vec<u32> = coefficients.iter().map(|row| row[k]).collect();
+
+        if button.iter().all(|&v| v == 0) {
             continue; // skip all-zero button
         }
 
-        // build the button from this column
-        let mut button = Vec::with_capacity(rows);
-        for p in 0..rows {
-            button.push(coefficients[p][k]);
-        }
         buttons.push(button);
+        columns.push(k);
     }
 
-    (buttons, rhs)
+    (buttons, rhs, columns)
 }
 
 fn build_system_from_buttons(buttons: Vec<Vec<u32>>, rhs: Vec<u32>) -> (Vec<Vec<u32>>, Vec<u32>) {
@@ -360,13 +335,16 @@ fn build_system_from_buttons(buttons: Vec<Vec<u32>>, rhs: Vec<u32>) -> (Vec<Vec<
 
 fn simplify(mut coefficients: Vec<Vec<u32>>, mut rhs: Vec<u32>) -> (Vec<Vec<u32>>, Vec<u32>) {
     let rows = coefficients.len();
-    let cols = coefficients[0].len();
 
-    println!("{} | {} |", rows, State { joltages: rhs.clone() });
-    for row in &coefficients {
-        print!("{} ", State { joltages: row.clone() });
-    }
-    println!();
+    debug!(
+        TARGET,
+        "simplifying {rows} rows, target{} rows{}",
+        State { joltages: rhs.clone() },
+        coefficients
+            .iter()
+            .map(|row| State { joltages: row.clone() }.to_string())
+            .collect::<String>()
+    );
 
     loop {
         let mut changed = false;
@@ -378,18 +356,8 @@ fn simplify(mut coefficients: Vec<Vec<u32>>, mut rhs: Vec<u32>) -> (Vec<Vec<u32>
                 }
 
                 // a[i] must be a superset of a[j]
-                let mut superset = true;
-                let mut nonzero = false;
-
-                for c in 0..cols {
-                    if coefficients[i][c] < coefficients[j][c] {
-                        superset = false;
-                        break;
-                    }
-                    if coefficients[j][c] != 0 {
-                        nonzero = true;
-                    }
-                }
+                let superset = coefficients[i].iter().zip(&coefficients[j]).all(|(a, b)| a >= b);
+                let nonzero = coefficients[j].iter().any(|&v| v != 0);
 
                 // skip if j is not subset of i OR j is all zeros
                 if !superset || !nonzero {
@@ -402,10 +370,13 @@ fn simplify(mut coefficients: Vec<Vec<u32>>, mut rhs: Vec<u32>) -> (Vec<Vec<u32>
                 }
 
                 // do row_i = row_i - row_j
-                for c in 0..cols {
-                    coefficients[i][c] -= coefficients[j][c];
+                let [row_i, row_j] = coefficients.get_disjoint_mut([i, j]).expect("rows are distinct");
+
+                for (a, b) in row_i.iter_mut().zip(row_j.iter()) {
+                    *a -= *b;
                 }
                 rhs[i] -= rhs[j];
+                trace!(TARGET, "row {i} -= row {j}");
 
                 changed = true;
                 break 'outer;
@@ -420,10 +391,14 @@ fn simplify(mut coefficients: Vec<Vec<u32>>, mut rhs: Vec<u32>) -> (Vec<Vec<u32>
     (coefficients, rhs)
 }
 
-fn extract_trivial_solutions(a: Vec<Vec<u32>>, b: Vec<u32>) -> (Vec<Vec<u32>>, Vec<u32>, u32) {
+/// Presses fixed for single buttons, as button and count.
+type Fixed = Vec<(usize, u32)>;
+
+/// Drops rows that name a single button, returning the presses they fix for that button.
+fn extract_trivial_solutions(a: Vec<Vec<u32>>, b: Vec<u32>) -> (Vec<Vec<u32>>, Vec<u32>, Fixed) {
     let mut remaining_a = Vec::new();
     let mut remaining_b = Vec::new();
-    let mut sum = 0_u32;
+    let mut presses = Vec::new();
 
     for (row, &rhs) in a.iter().zip(b.iter()) {
         let mut count = 0;
@@ -438,7 +413,7 @@ fn extract_trivial_solutions(a: Vec<Vec<u32>>, b: Vec<u32>) -> (Vec<Vec<u32>>, V
         }
 
         if count == 1 {
-            sum += rhs;
+            presses.push((row.iter().position(|&v| v != 0).unwrap(), rhs));
             continue;
         }
 
@@ -446,40 +421,76 @@ fn extract_trivial_solutions(a: Vec<Vec<u32>>, b: Vec<u32>) -> (Vec<Vec<u32>>, V
         remaining_b.push(rhs);
     }
 
-    (remaining_a, remaining_b, sum)
+    (remaining_a, remaining_b, presses)
 }
 
 struct Solver {
     target: Vec<u32>,
     buttons: Vec<Vec<u32>>,
     len: usize,
+    /// Bits needed by each counter in a memo key, counters never passing their target.
+    widths: Vec<u32>,
 }
 
-#[derive(Hash, Eq, PartialEq)]
-struct MemoKey {
-    current: Vec<u32>,
-    remaining_mask: u64,
+struct Branch {
+    /// Counter whose target the subject buttons must reach in this step.
+    subject_index: usize,
+    subject_buttons: Vec<usize>,
+    rest: Vec<usize>,
+    needed: u32,
 }
 
+/// Fewest further presses from a state, keyed by the counters and the buttons left.
+type Table = Memo<Key, Option<u32>>;
+
 impl Solver {
     fn new(target: Vec<u32>, buttons: Vec<Vec<u32>>) -> Self {
         let len = target.len();
-        Self { target, buttons, len }
+        let widths = target.iter().map(|t| u32::BITS - t.leading_zeros()).collect();
+
+        Self {
+            target,
+            buttons,
+            len,
+            widths,
+        }
     }
 
-    fn solve(&self) -> Option<u32> {
-        debug_assert!(self.buttons.len() <= 64);
+    /// Fewest presses, with the presses of each button when the search ran to completion.
+    fn solve(&self, meter: &mut Meter, memo: &mut Table) -> (Option<u32>, Option<Vec<u32>>) {
         let ids: Vec<usize> = (0..self.buttons.len()).collect();
         let current = vec![0_u32; self.len];
-        let mut memo: HashMap<MemoKey, Option<u32>> = HashMap::new();
-        self.go(&ids, &current, &mut memo)
+        let count = self.go(&ids, &current, memo, meter, 0);
+
+        if meter.exceeded().is_some() || count.is_none() {
+            return (count, None);
+        }
+
+        (count, self.presses(memo))
+    }
+
+    fn key(&self, remaining_buttons: &[usize], current: &[u32]) -> Key {
+        let mut key = KeyBuilder::new();
+
+        for (&counter, &width) in current.iter().zip(&self.widths) {
+            key.push(u64::from(counter), width);
+        }
+
+        for (i, word) in self.remaining_mask(remaining_buttons).into_iter().enumerate() {
+            key.push(word, (self.buttons.len() - i * 64).min(64) as u32);
+        }
+
+        key.finish()
     }
 
-    fn remaining_mask(&self, remaining_buttons: &[usize]) -> u64 {
-        let mut mask = 0_u64;
+    /// The buttons left as a bitset, button `i` being bit `i % 64` of word `i / 64`.
+    fn remaining_mask(&self, remaining_buttons: &[usize]) -> Vec<u64> {
+        let mut mask = vec![0_u64; self.buttons.len().div_ceil(64)];
+
         for &id in remaining_buttons {
-            mask |= 1_u64 << id;
+            mask[id / 64] |= 1 << (id % 64);
         }
+
         mask
     }
 
@@ -501,47 +512,24 @@ impl Solver {
             .unwrap()
     }
 
-    /// Returns minimal additional presses needed from this state.
-    fn go(
-        &self,
-        remaining_buttons: &[usize],
-        current: &[u32],
-        memo: &mut HashMap<MemoKey, Option<u32>>,
-    ) -> Option<u32> {
-        let key = MemoKey {
-            current: current.to_vec(),
-            remaining_mask: self.remaining_mask(remaining_buttons),
-        };
-
-        if let Some(&cached) = memo.get(&key) {
-            return cached;
-        }
-
-        // base case
-        if remaining_buttons.is_empty() {
-            let ans = if self.target == current { Some(0) } else { None };
-            memo.insert(key, ans);
-            return ans;
-        }
-
+    /// Picks the counter the next presses settle, or `None` when the remaining buttons cannot reach the target.
+    fn branch(&self, remaining_buttons: &[usize], current: &[u32]) -> Option<Branch> {
         let subject_index = self.least_frequent(remaining_buttons);
 
-        let (subject_buttons, remaining_buttons_vec): (Vec<usize>, Vec<usize>) = remaining_buttons
+        let (subject_buttons, rest): (Vec<usize>, Vec<usize>) = remaining_buttons
             .iter()
             .copied()
             .partition(|&i| self.buttons[i][subject_index] == 1);
 
-        let remaining_buttons = remaining_buttons_vec;
-
         let needed = self.target[subject_index].saturating_sub(current[subject_index]);
 
         // same pruning semantics as your last working version (min over {0,1} includes zeros)
-        for i in 0..self.len {
+        for (i, (target, &current)) in self.target.iter().zip(current).enumerate() {
             if i == subject_index {
                 continue;
             }
 
-            let space = self.target[i].saturating_sub(current[i]);
+            let space = target.saturating_sub(current);
             let min_cost = subject_buttons
                 .iter()
                 .map(|&b_id| self.buttons[b_id][i])
@@ -549,13 +537,28 @@ impl Solver {
                 .unwrap_or(0);
 
             if (needed as u64 * min_cost as u64) > space as u64 {
-                memo.insert(key, None);
                 return None;
             }
         }
 
+        Some(Branch {
+            subject_index,
+            subject_buttons,
+            rest,
+            needed,
+        })
+    }
+
+    /// Every way of pressing the subject buttons that settles the subject counter without overshooting, as the
+    /// presses of each subject button and the counters they lead to.
+    fn choices<'a>(
+        &'a self,
+        branch: &'a Branch,
+        current: &'a [u32],
+    ) -> impl Iterator<Item = (Vec<u32>, Vec<u32>)> + 'a {
         // per-button max presses
-        let maxes = subject_buttons
+        let maxes = branch
+            .subject_buttons
             .iter()
             .map(|&button_id| {
                 self.buttons[button_id]
@@ -568,9 +571,7 @@ impl Solver {
             })
             .collect::<Vec<_>>();
 
-        let mut best: Option<u32> = None;
-
-        for it in Permutations::new(maxes, needed) {
+        Permutations::new(maxes, branch.needed).filter_map(move |it| {
             // build result Vec<u32>
             let mut result = current.to_vec();
 
@@ -578,33 +579,130 @@ impl Solver {
                 if count == 0 {
                     continue;
                 }
-                let b_id = subject_buttons[idx];
+                let b_id = branch.subject_buttons[idx];
                 let button = &self.buttons[b_id];
 
-                for d in 0..self.len {
-                    result[d] += button[d] * count; // button[d] is 0/1
+                for (total, &step) in result.iter_mut().zip(button) {
+                    *total += step * count; // step is 0/1
                 }
             }
 
             // overshoot?
-            if (0..self.len).any(|d| self.target[d] < result[d]) {
-                continue;
+            if self.target.iter().zip(&result).any(|(target, total)| target < total) {
+                return None;
             }
 
             // must satisfy the chosen dimension exactly
-            if result[subject_index] != self.target[subject_index] {
-                continue;
+            if result[branch.subject_index] != self.target[branch.subject_index] {
+                return None;
+            }
+
+            Some((it, result))
+        })
+    }
+
+    /// Presses of each button in a cheapest solution, replaying the choices that the memo of a finished search
+    /// shows to be optimal. Entries a bounded memo dropped are searched again.
+    fn presses(&self, memo: &mut Table) -> Option<Vec<u32>> {
+        let mut meter = Budget::default().start();
+        let mut presses = vec![0; self.buttons.len()];
+        let mut remaining_buttons: Vec<usize> = (0..self.buttons.len()).collect();
+        let mut current = vec![0_u32; self.len];
+
+        while !remaining_buttons.is_empty() {
+            let total = self.go(&remaining_buttons, &current, memo, &mut meter, 0)?;
+            let branch = self.branch(&remaining_buttons, &current)?;
+            let (counts, result) = self.choices(&branch, &current).find(|(counts, result)| {
+                self.go(&branch.rest, result, memo, &mut meter, 0) == Some(total - counts.iter().sum::<u32>())
+            })?;
+
+            for (idx, &count) in counts.iter().enumerate() {
+                presses[branch.subject_buttons[idx]] += count;
+            }
+
+            remaining_buttons = branch.rest;
+            current = result;
+        }
+
+        Some(presses)
+    }
+
+    /// Returns minimal additional presses needed from this state.
+    fn go(
+        &self,
+        remaining_buttons: &[usize],
+        current: &[u32],
+        memo: &mut Table,
+        meter: &mut Meter,
+        depth: usize,
+    ) -> Option<u32> {
+        // Once out of budget every call returns at once, callers keep the best they found before
+        if !meter.tick() || !meter.memo(memo.len()) {
+            return None;
+        }
+
+        let key = self.key(remaining_buttons, current);
+
+        if let Some(cached) = memo.get(&key) {
+            trace!(TARGET, "depth {depth}: memo hit {current:?} -> {cached:?}");
+            return cached;
+        }
+
+        // base case
+        if remaining_buttons.is_empty() {
+            let ans = if self.target == current { Some(0) } else { None };
+            memo.insert(key, ans);
+            return ans;
+        }
+
+        let Some(branch) = self.branch(remaining_buttons, current) else {
+            memo.insert(key, None);
+            return None;
+        };
+
+        trace!(
+            TARGET,
+            "depth {depth}: counter {} needs {} from {} buttons",
+            branch.subject_index,
+            branch.needed,
+            branch.subject_buttons.len()
+        );
+
+        let mut best: Option<u32> = None;
+
+        for (counts, result) in self.choices(&branch, current) {
+            if meter.exceeded().is_some() {
+                break;
             }
 
-            let presses_here = it.iter().sum::<u32>();
+            let presses_here = counts.iter().sum::<u32>();
 
-            if let Some(extra) = self.go(&remaining_buttons, &result, memo) {
+            if let Some(extra) = self.go(&branch.rest, &result, memo, meter, depth + 1) {
                 let total = presses_here + extra;
                 best = Some(best.map_or(total, |b| b.min(total)));
             }
         }
 
-        memo.insert(key, best);
+        // A partial minimum must not be cached
+        if meter.exceeded().is_none() {
+            memo.insert(key, best);
+        }
+
         best
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solves_systems_of_more_than_64_buttons() {
+        let buttons = [vec![vec![1, 0]; 69], vec![vec![0, 1]]].concat();
+        let solver = Solver::new(vec![3, 2], buttons);
+        let (count, presses) = solver.solve(&mut Budget::default().start(), &mut Memo::new());
+
+        assert_eq!(count, Some(5));
+        assert_eq!(presses.map(|presses| presses.iter().sum::<u32>()), Some(5));
+    }
+}
@@ -0,0 +1,24 @@
+//! Checks a press vector by pressing the buttons, independently of how the solver reduced the system.
+
+use machine::Machine;
+
+/// Total number of presses, if pressing each button the given number of times yields the machine's joltages.
+pub fn presses(machine: &Machine, presses: &[u32]) -> Result<u32, String> {
+    if presses.len() != machine.buttons().len() {
+        return Err(format!("{} press counts for {} buttons", presses.len(), machine.buttons().len()));
+    }
+
+    let mut joltages = vec![0; machine.width()];
+
+    for (button, &count) in machine.buttons().iter().zip(presses) {
+        for &counter in button {
+            joltages[counter] += count;
+        }
+    }
+
+    if joltages != machine.joltages() {
+        return Err(format!("presses give {joltages:?}, {machine} needs {:?}", machine.joltages()));
+    }
+
+    Ok(presses.iter().sum())
+}
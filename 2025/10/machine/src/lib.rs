@@ -0,0 +1,307 @@
+use std::{
+    error,
+    fmt::{self, Display, Formatter},
+    io,
+    num::ParseIntError,
+    path::Path,
+    str::FromStr,
+};
+
+use aoc::{
+    input,
+    parse::{self, Mode},
+};
+
+/// Indicator lights as a bitset, light `i` being bit `i % 64` of word `i / 64`.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Lights {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl Lights {
+    pub fn new(len: usize) -> Self {
+        Lights {
+            words: vec![0; len.div_ceil(64)],
+            len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, i: usize) -> bool {
+        self.words[i / 64] >> (i % 64) & 1 == 1
+    }
+
+    pub fn toggle(&mut self, i: usize) {
+        self.words[i / 64] ^= 1 << (i % 64);
+    }
+
+    pub fn xor(&mut self, other: &Lights) {
+        for (word, other) in self.words.iter_mut().zip(&other.words) {
+            *word ^= other;
+        }
+    }
+
+    pub fn words(&self) -> &[u64] {
+        &self.words
+    }
+}
+
+impl Display for Lights {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "[")?;
+
+        for i in 0..self.len {
+            write!(f, "{}", if self.get(i) { '#' } else { '.' })?;
+        }
+
+        write!(f, "]")
+    }
+}
+
+pub struct Machine {
+    indicators: Lights,
+    buttons: Vec<Vec<usize>>,
+    joltages: Vec<u32>,
+}
+
+impl Machine {
+    pub fn width(&self) -> usize {
+        self.indicators.len()
+    }
+
+    pub fn indicators(&self) -> &Lights {
+        &self.indicators
+    }
+
+    /// Light indices toggled by each button, in input order.
+    pub fn buttons(&self) -> &[Vec<usize>] {
+        &self.buttons
+    }
+
+    pub fn button_mask(&self, i: usize) -> Lights {
+        let mut mask = Lights::new(self.width());
+
+        for &light in &self.buttons[i] {
+            mask.toggle(light);
+        }
+
+        mask
+    }
+
+    /// Joltage increase of each counter when button `i` is pressed once.
+    pub fn button_vector(&self, i: usize) -> Vec<u32> {
+        let mut vector = vec![0; self.width()];
+
+        for &light in &self.buttons[i] {
+            vector[light] += 1;
+        }
+
+        vector
+    }
+
+    pub fn joltages(&self) -> &[u32] {
+        &self.joltages
+    }
+}
+
+fn join<T: ToString>(values: &[T]) -> String {
+    values.iter().map(|n| n.to_string()).collect::<Vec<_>>().join(",")
+}
+
+impl Display for Machine {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.indicators)?;
+
+        for button in &self.buttons {
+            write!(f, " ({})", join(button))?;
+        }
+
+        write!(f, " {{{}}}", join(&self.joltages))
+    }
+}
+
+#[derive(Debug)]
+pub enum ParseMachineError {
+    InvalidSyntax,
+    InvalidNumber(ParseIntError),
+    NonCanonicalNumber(String),
+    LightOutOfRange(usize),
+    JoltageCount(usize, usize),
+}
+
+impl Display for ParseMachineError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidSyntax => write!(f, "Invalid syntax"),
+            Self::InvalidNumber(e) => write!(f, "Invalid number: {e}"),
+            Self::NonCanonicalNumber(s) => write!(f, "Number '{s}' is not written canonically"),
+            Self::LightOutOfRange(i) => write!(f, "Button refers to light {i} which does not exist"),
+            Self::JoltageCount(expected, found) => write!(f, "Expected {expected} joltages, got {found}"),
+        }
+    }
+}
+
+impl error::Error for ParseMachineError {}
+
+/// Parses a comma separated list of numbers, rejecting anything `Display` would not print back.
+fn parse_list<T>(s: &str) -> Result<Vec<T>, ParseMachineError>
+where
+    T: FromStr<Err = ParseIntError>,
+{
+    s.split(',')
+        .map(|number| {
+            if number.is_empty() || !number.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(ParseMachineError::InvalidSyntax);
+            }
+
+            if number.len() > 1 && number.starts_with('0') {
+                return Err(ParseMachineError::NonCanonicalNumber(number.to_owned()));
+            }
+
+            number.parse::<T>().map_err(ParseMachineError::InvalidNumber)
+        })
+        .collect()
+}
+
+fn enclosed(s: &str, open: char, close: char) -> Option<&str> {
+    s.strip_prefix(open)?.strip_suffix(close)
+}
+
+impl FromStr for Machine {
+    type Err = ParseMachineError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens: Vec<&str> = s.split(' ').collect();
+        let [first, middle @ .., last] = tokens.as_slice() else {
+            return Err(ParseMachineError::InvalidSyntax);
+        };
+
+        let target = enclosed(first, '[', ']').ok_or(ParseMachineError::InvalidSyntax)?;
+
+        if target.is_empty() || !target.chars().all(|c| c == '.' || c == '#') {
+            return Err(ParseMachineError::InvalidSyntax);
+        }
+
+        let mut indicators = Lights::new(target.len());
+
+        for (i, _) in target.char_indices().filter(|(_, c)| *c == '#') {
+            indicators.toggle(i);
+        }
+
+        let buttons = middle
+            .iter()
+            .map(|token| {
+                let button = parse_list::<usize>(enclosed(token, '(', ')').ok_or(ParseMachineError::InvalidSyntax)?)?;
+
+                match button.iter().find(|light| **light >= indicators.len()) {
+                    Some(light) => Err(ParseMachineError::LightOutOfRange(*light)),
+                    None => Ok(button),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let joltages = parse_list::<u32>(enclosed(last, '{', '}').ok_or(ParseMachineError::InvalidSyntax)?)?;
+
+        if joltages.len() != indicators.len() {
+            return Err(ParseMachineError::JoltageCount(indicators.len(), joltages.len()));
+        }
+
+        Ok(Machine {
+            indicators,
+            buttons,
+            joltages,
+        })
+    }
+}
+
+pub fn read_machines(path: &Path, mode: Mode) -> io::Result<Vec<Machine>> {
+    parse::records(&input::read(path)?, mode)
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc::{prop, rng::Rng};
+
+    use super::*;
+
+    #[derive(Clone, Debug)]
+    struct Case {
+        lit: Vec<bool>,
+        buttons: Vec<Vec<usize>>,
+        joltages: Vec<u32>,
+    }
+
+    impl Case {
+        fn machine(&self) -> Machine {
+            let mut indicators = Lights::new(self.lit.len());
+
+            for (i, _) in self.lit.iter().enumerate().filter(|(_, lit)| **lit) {
+                indicators.toggle(i);
+            }
+
+            Machine {
+                indicators,
+                buttons: self.buttons.clone(),
+                joltages: self.joltages.clone(),
+            }
+        }
+    }
+
+    fn generate(rng: &mut Rng) -> Case {
+        // Past two words of lights now and then
+        let width = if rng.chance(0.2) { rng.between(65, 150) } else { rng.between(1, 10) } as usize;
+        let buttons = (0..rng.below(8))
+            .map(|_| (0..rng.between(1, 4)).map(|_| rng.index(width)).collect())
+            .collect();
+
+        Case {
+            lit: (0..width).map(|_| rng.chance(0.5)).collect(),
+            buttons,
+            joltages: (0..width).map(|_| rng.below(1000) as u32).collect(),
+        }
+    }
+
+    fn shrink(case: &Case) -> Vec<Case> {
+        prop::shrink_list(&case.buttons)
+            .into_iter()
+            .map(|buttons| Case { buttons, ..case.clone() })
+            .collect()
+    }
+
+    fn round_trips(case: &Case) -> Result<(), String> {
+        let machine = case.machine();
+        let parsed: Machine = machine.to_string().parse().map_err(|e: ParseMachineError| e.to_string())?;
+
+        if parsed.indicators != machine.indicators || parsed.buttons != machine.buttons {
+            return Err(format!("read back as {parsed}"));
+        }
+
+        if parsed.joltages != machine.joltages {
+            return Err(format!("joltages read back as {:?}", parsed.joltages));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn display_round_trips() {
+        if let Err(failure) = prop::check(&mut Rng::new(1), 1000, generate, shrink, round_trips) {
+            panic!("{}: {}", failure.shrunk.machine(), failure.reason);
+        }
+    }
+
+    #[test]
+    fn rejects_what_display_never_prints() {
+        for line in ["[.#] (01) {1,2}", "[.#] (2) {1,2}", "[.#] (0) {1}", "[.#] (0)  {1,2}", "[] {}"] {
+            assert!(line.parse::<Machine>().is_err(), "{line}");
+        }
+    }
+}
@@ -2,12 +2,19 @@ use std::{
     collections::HashMap,
     error,
     fmt::{self, Display, Formatter},
-    fs::File,
-    io::{self, BufRead, BufReader},
+    io,
     path::Path,
     str::FromStr,
 };
 
+use aoc::{
+    debug,
+    input,
+    memo::{self, Memo},
+    parse::Mode,
+    trace,
+};
+
 struct Server {
     name: String,
     connections: Vec<String>,
@@ -32,62 +39,104 @@ impl FromStr for Server {
     type Err = ParseServerError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let outer = s.splitn(2, ":").collect::<Vec<_>>();
-
-        if outer.len() != 2 {
-            return Result::Err(ParseServerError::InvalidLineFormat(s.to_owned()));
-        }
+        let Some((name, connections)) = s.split_once(':') else {
+            return Err(ParseServerError::InvalidLineFormat(s.to_owned()));
+        };
 
-        let name = outer[0].to_owned();
-        let connections = outer[1]
-            .trim()
-            .split(" ")
+        let name = name.to_owned();
+        let connections = connections
+            .split_ascii_whitespace()
             .map(|c| c.to_owned())
             .collect::<Vec<String>>();
 
-        if connections.len() == 0 {
-            return Result::Err(ParseServerError::InvalidLineFormat(s.to_owned()));
+        if name.is_empty() || connections.is_empty() {
+            return Err(ParseServerError::InvalidLineFormat(s.to_owned()));
         }
 
         Ok(Self { name, connections })
     }
 }
 
+const TARGET: &str = "day11";
+
 struct Input {
-    servers: HashMap<String, Vec<String>>,
+    /// Index of every server named in the input.
+    ids: HashMap<String, usize>,
+    /// Servers each server connects to, by index.
+    connections: Vec<Vec<usize>>,
+    terminal: usize,
+    /// Servers every counted path must visit, each setting its bit of the mask.
+    required: [usize; 2],
 }
 
 impl Input {
-    fn load(path: &Path) -> io::Result<Self> {
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
-
-        reader
-            .lines()
-            .map(|line| {
-                line?
-                    .parse::<Server>()
-                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
-            })
-            .collect::<Result<Vec<Server>, _>>()
-            .map(|servers| Input {
-                servers: servers.into_iter().map(|s| (s.name, s.connections)).collect(),
-            })
+    fn load(path: &Path, mode: Mode) -> io::Result<Self> {
+        let mut servers = HashMap::new();
+
+        for (i, line) in input::read(path)?.lines().enumerate() {
+            let server = match line.parse::<Server>() {
+                Ok(server) => server,
+                Err(e) => {
+                    mode.reject(i + 1, e)?;
+                    continue;
+                }
+            };
+
+            if servers.contains_key(&server.name) {
+                mode.reject(i + 1, format!("duplicate server '{}'", server.name))?;
+                continue;
+            }
+
+            servers.insert(server.name, server.connections);
+        }
+
+        Ok(Input::number(servers))
+    }
+
+    /// Numbers the servers, so the solver's memo is keyed by index instead of by cloned name.
+    fn number(servers: HashMap<String, Vec<String>>) -> Self {
+        let mut input = Input {
+            ids: HashMap::new(),
+            connections: Vec::new(),
+            terminal: 0,
+            required: [0; 2],
+        };
+
+        input.terminal = input.id(TERMINAL);
+        input.required = REQUIRED.map(|name| input.id(name));
+
+        for (name, connections) in &servers {
+            let id = input.id(name);
+
+            input.connections[id] = connections.iter().map(|c| input.id(c)).collect();
+        }
+
+        input
+    }
+
+    fn id(&mut self, name: &str) -> usize {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+
+        let id = self.connections.len();
+
+        self.ids.insert(name.to_owned(), id);
+        self.connections.push(Vec::new());
+        id
     }
 }
 
 impl Input {
-    fn count_paths(self: &Input, parent: &str, mask: i32, cache: &mut HashMap<(String, i32), u64>) -> u64 {
-        let key = (parent.to_owned(), mask);
-
-        if let Some(count) = cache.get(&key) {
-            return *count;
+    fn count_paths(&self, parent: usize, mask: u8, memo: &mut Memo<(usize, u8), u64>) -> u64 {
+        if let Some(count) = memo.get(&(parent, mask)) {
+            return count;
         }
 
         let mut count = 0;
 
-        for child in &self.servers[parent] {
-            if child == TERMINAL {
+        for &child in &self.connections[parent] {
+            if child == self.terminal {
                 if mask == 3 {
                     count += 1;
                 }
@@ -95,16 +144,16 @@ impl Input {
             }
 
             let mut mask = mask;
-            if child == "fft" {
-                mask |= 1;
-            } else if child == "dac" {
-                mask |= 2;
+            for (bit, &required) in self.required.iter().enumerate() {
+                if child == required {
+                    mask |= 1 << bit;
+                }
             }
 
-            count += self.count_paths(child, mask, cache);
+            count += self.count_paths(child, mask, memo);
         }
 
-        cache.insert(key, count);
+        memo.insert((parent, mask), count);
 
         count
     }
@@ -112,11 +161,20 @@ impl Input {
 
 const START: &str = "svr";
 const TERMINAL: &str = "out";
+const REQUIRED: [&str; 2] = ["fft", "dac"];
 
 fn main() -> io::Result<()> {
-    let input = Input::load(Path::new("../input"))?;
-    let count = input.count_paths(START, 0, &mut HashMap::new());
+    trace::init()?;
+
+    let input = Input::load(Path::new("../input"), Mode::from_args())?;
+    let Some(&start) = input.ids.get(START) else {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, format!("no server '{START}'")));
+    };
+
+    let mut memo = Memo::with_capacity(memo::capacity_from_args()?);
+    let count = input.count_paths(start, 0, &mut memo);
 
+    debug!(TARGET, "memo {}", memo.stats());
     println!("{count}");
 
     Ok(())
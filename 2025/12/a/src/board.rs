@@ -0,0 +1,117 @@
+use aoc::grid::{BitGrid, Grid};
+
+use crate::{Region, Transformer};
+
+/// Occupancy of a region while shapes are placed into it. A cell is occupied once a shape covers it or it is
+/// deliberately left empty.
+pub trait Board<'a> {
+    fn new(region: &Region, transformer: &'a Transformer) -> Self;
+
+    /// The first free cell at or after `from` in row-major order.
+    fn first_free(&self, from: (usize, usize)) -> Option<(usize, usize)>;
+
+    fn fits(&self, shape: usize, variant: usize, x: usize, y: usize) -> bool;
+
+    /// Places a variant with its top left corner at `(x, y)`, or removes it when it is already there.
+    fn toggle(&mut self, shape: usize, variant: usize, x: usize, y: usize);
+
+    fn set(&mut self, x: usize, y: usize, occupied: bool);
+}
+
+/// Tests and places shapes cell by cell and looks for free cells from the top left corner every time. This is how
+/// the solver used to work, kept as the baseline for `--bench`.
+pub struct CellBoard<'a> {
+    grid: Grid,
+    transformer: &'a Transformer,
+}
+
+impl<'a> Board<'a> for CellBoard<'a> {
+    fn new(region: &Region, transformer: &'a Transformer) -> Self {
+        CellBoard {
+            grid: Grid::new(region.width, region.height, b' '),
+            transformer,
+        }
+    }
+
+    fn first_free(&self, _: (usize, usize)) -> Option<(usize, usize)> {
+        self.grid.positions(b' ').next()
+    }
+
+    fn fits(&self, shape: usize, variant: usize, x: usize, y: usize) -> bool {
+        let variant = &self.transformer.variants(shape)[variant];
+
+        variant
+            .filled
+            .iter()
+            .all(|&(dx, dy)| self.grid.get(x + dx, y + dy) == Some(b' '))
+    }
+
+    fn toggle(&mut self, shape: usize, variant: usize, x: usize, y: usize) {
+        let variant = &self.transformer.variants(shape)[variant];
+
+        for &(dx, dy) in &variant.filled {
+            let c = if self.grid.get(x + dx, y + dy) == Some(b' ') { b'#' } else { b' ' };
+            self.grid.set(x + dx, y + dy, c);
+        }
+    }
+
+    fn set(&mut self, x: usize, y: usize, occupied: bool) {
+        self.grid.set(x, y, if occupied { b'.' } else { b' ' });
+    }
+}
+
+/// Keeps occupancy as row bit masks and tests a placement with one AND per row word, using the masks of every
+/// variant shifted to every column it fits at, computed once per region.
+pub struct RowBoard {
+    grid: BitGrid,
+    /// Indexed by shape, variant and column.
+    masks: Vec<Vec<Vec<Vec<u64>>>>,
+}
+
+impl<'a> Board<'a> for RowBoard {
+    fn new(region: &Region, transformer: &'a Transformer) -> Self {
+        let grid = BitGrid::new(region.width, region.height);
+        let stride = grid.stride();
+        let masks = (0..transformer.shapes())
+            .map(|shape| {
+                transformer
+                    .variants(shape)
+                    .iter()
+                    .map(|variant| {
+                        (0..(region.width + 1).saturating_sub(variant.width))
+                            .map(|x| {
+                                let mut mask = vec![0; variant.height * stride];
+
+                                for &(dx, dy) in &variant.filled {
+                                    mask[dy * stride + (x + dx) / 64] |= 1 << ((x + dx) % 64);
+                                }
+
+                                mask
+                            })
+                            .collect()
+                    })
+                    .collect()
+            })
+            .collect();
+
+        RowBoard { grid, masks }
+    }
+
+    fn first_free(&self, (x, y): (usize, usize)) -> Option<(usize, usize)> {
+        self.grid.first_clear_from(x, y)
+    }
+
+    fn fits(&self, shape: usize, variant: usize, x: usize, y: usize) -> bool {
+        self.masks[shape][variant]
+            .get(x)
+            .is_some_and(|mask| self.grid.fits_words(y, mask))
+    }
+
+    fn toggle(&mut self, shape: usize, variant: usize, x: usize, y: usize) {
+        self.grid.toggle_words(y, &self.masks[shape][variant][x]);
+    }
+
+    fn set(&mut self, x: usize, y: usize, occupied: bool) {
+        self.grid.set(x, y, occupied);
+    }
+}
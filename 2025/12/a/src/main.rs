@@ -1,84 +1,67 @@
+mod board;
+mod verify;
+
 use std::{
     collections::HashSet,
-    fs::File,
-    io::{self, BufRead, BufReader, Lines},
+    env,
+    io,
     path::Path,
     sync::LazyLock,
+    time::Instant,
 };
 
+use aoc::{
+    budget::{Budget, Meter, Outcome},
+    debug,
+    grid::Grid,
+    input,
+    parallel::{Pool, Solved},
+    parse::{self, Mode},
+    trace,
+};
+use board::{Board, CellBoard, RowBoard};
 use regex::Regex;
 
-#[derive(Clone, PartialEq, Eq, Hash)]
-struct Grid {
-    data: Vec<char>,
+const TARGET: &str = "day12";
+
+struct Region {
     width: usize,
     height: usize,
+    shape_quantities: Vec<usize>,
 }
 
-impl Grid {
-    #[inline]
-    fn index(&self, x: usize, y: usize) -> usize {
-        self.width * y + x
-    }
-
-    fn new(width: usize, height: usize) -> Self {
-        let data = vec![' '; width * height];
-        Self { width, height, data }
-    }
-
-    fn get(&self, x: usize, y: usize) -> Option<char> {
-        if x >= self.width {
-            return None;
-        }
-
-        if y >= self.height {
-            return None;
-        }
-
-        Some(self.data[self.index(x, y)])
-    }
-
-    fn set(&mut self, x: usize, y: usize, c: char) {
-        if x >= self.width {
-            return;
-        }
-
-        if y >= self.height {
-            return;
-        }
-
-        let index = self.index(x, y);
-        self.data[index] = c;
-    }
-
-    fn filled_cells(&self) -> Vec<(usize, usize)> {
-        let mut filled = Vec::new();
-
-        for (idx, c) in self.data.iter().enumerate() {
-            if *c != ' ' {
-                filled.push((idx % self.width, idx / self.width));
-            }
-        }
+/// A present placed at `(x, y)`, the top left corner of its shape after applying `TRANSFORMATIONS[transformation]`.
+#[derive(Clone, Copy, Debug)]
+struct Placement {
+    shape: usize,
+    transformation: usize,
+    x: usize,
+    y: usize,
+}
 
-        filled
-    }
+enum Fit {
+    Packed(Vec<Placement>),
+    Impossible,
 }
 
-struct Region {
-    width: usize,
-    height: usize,
-    shape_quantities: Vec<usize>,
+impl Fit {
+    fn fits(&self) -> bool {
+        matches!(self, Fit::Packed(_))
+    }
 }
 
-struct Solver<'a> {
-    region: &'a Region,
+struct Solver<'r, 'a> {
+    region: &'r Region,
     transforms: &'a Transformer,
 }
 
-impl<'a> Solver<'_> {
-    fn can_fit(&'a self) -> bool {
-        let mut grid = Grid::new(self.region.width, self.region.height);
+impl<'a> Solver<'_, 'a> {
+    /// Whether the presents fit, or `None` when the budget ran out before the search could tell.
+    fn can_fit<B: Board<'a>>(&self, budget: &Budget) -> Outcome<Option<Fit>> {
+        let mut meter = budget.start();
+        let mut board = B::new(self.region, self.transforms);
         let mut remaining = self.region.shape_quantities.clone();
+        let mut placements = Vec::new();
 
         let area = self.region.width * self.region.height;
         let total_filled: usize = self
@@ -90,22 +73,43 @@ impl<'a> Solver<'_> {
             .sum();
 
         if total_filled > area {
-            return false;
+            return meter.finish(Some(Fit::Impossible));
         }
 
         let empty_budget = area - total_filled;
+        let fits = self.can_fit_board(&mut board, &mut meter, &mut remaining, &mut placements, empty_budget, (0, 0));
 
-        self.can_fit_grid(&mut grid, &mut remaining, empty_budget, 0)
+        if meter.exceeded().is_some() {
+            return meter.finish(None);
+        }
+
+        let outcome = meter.finish(Some(if fits { Fit::Packed(placements) } else { Fit::Impossible }));
+
+        debug!(TARGET, "{} after {}", if fits { "packed" } else { "impossible" }, outcome.stats);
+        outcome
     }
 
-    fn can_fit_grid(&self, grid: &mut Grid, remaining: &mut [usize], remaining_empty: usize, depth: usize) -> bool {
+    /// Every cell before `from` is occupied, so the search for the next free cell resumes there.
+    fn can_fit_board<B: Board<'a>>(
+        &self,
+        board: &mut B,
+        meter: &mut Meter,
+        remaining: &mut [usize],
+        placements: &mut Vec<Placement>,
+        remaining_empty: usize,
+        from: (usize, usize),
+    ) -> bool {
+        if !meter.tick() {
+            return false;
+        }
+
         let total_remaining: usize = remaining.iter().sum();
 
         if total_remaining == 0 {
             return true;
         }
 
-        let Some((anchor_x, anchor_y)) = self.first_empty_cell(grid) else {
+        let Some((anchor_x, anchor_y)) = board.first_free(from) else {
             return false;
         };
 
@@ -116,7 +120,7 @@ impl<'a> Solver<'_> {
 
             let variants = self.transforms.variants(shape_id);
 
-            for variant in variants {
+            for (variant_id, variant) in variants.iter().enumerate() {
                 for &(dx, dy) in &variant.filled {
                     if anchor_x < dx || anchor_y < dy {
                         continue;
@@ -125,69 +129,45 @@ impl<'a> Solver<'_> {
                     let x = anchor_x - dx;
                     let y = anchor_y - dy;
 
-                    if x + variant.grid.width > self.region.width || y + variant.grid.height > self.region.height {
-                        continue;
-                    }
-
-                    if self.can_place_at(grid, variant, x, y) {
-                        self.place_at(grid, variant, x, y, '#');
+                    if board.fits(shape_id, variant_id, x, y) {
+                        board.toggle(shape_id, variant_id, x, y);
                         remaining[shape_id] -= 1;
-
-                        if self.can_fit_grid(grid, remaining, remaining_empty, depth + 1) {
+                        placements.push(Placement {
+                            shape: shape_id,
+                            transformation: variant.transformation,
+                            x,
+                            y,
+                        });
+
+                        if self.can_fit_board(board, meter, remaining, placements, remaining_empty, (anchor_x, anchor_y))
+                        {
                             return true;
                         }
 
+                        placements.pop();
                         remaining[shape_id] += 1;
-                        self.place_at(grid, variant, x, y, ' ');
+                        board.toggle(shape_id, variant_id, x, y);
                     }
                 }
             }
         }
 
         if remaining_empty > 0 {
-            grid.set(anchor_x, anchor_y, '.');
+            board.set(anchor_x, anchor_y, true);
 
-            if self.can_fit_grid(grid, remaining, remaining_empty - 1, depth + 1) {
+            if self.can_fit_board(board, meter, remaining, placements, remaining_empty - 1, (anchor_x, anchor_y)) {
                 return true;
             }
 
-            grid.set(anchor_x, anchor_y, ' ');
+            board.set(anchor_x, anchor_y, false);
         }
 
         false
     }
-
-    fn first_empty_cell(&self, grid: &Grid) -> Option<(usize, usize)> {
-        for (idx, c) in grid.data.iter().enumerate() {
-            if *c == ' ' {
-                return Some((idx % grid.width, idx / grid.width));
-            }
-        }
-
-        None
-    }
-
-    fn can_place_at(&self, grid: &Grid, variant: &Variant, x: usize, y: usize) -> bool {
-        for &(dx, dy) in &variant.filled {
-            let gx = x + dx;
-            let gy = y + dy;
-
-            if grid.get(gx, gy) != Some(' ') {
-                return false;
-            }
-        }
-
-        true
-    }
-
-    fn place_at(&self, grid: &mut Grid, variant: &Variant, x: usize, y: usize, c: char) {
-        for &(dx, dy) in &variant.filled {
-            grid.set(x + dx, y + dy, c);
-        }
-    }
 }
 
 struct Transformation {
+    name: &'static str,
     swap_xy: bool,
     invert_x: bool,
     invert_y: bool,
@@ -195,13 +175,13 @@ struct Transformation {
 
 impl Transformation {
     fn apply(&self, grid: &Grid) -> Grid {
-        let old_w = grid.width;
-        let old_h = grid.height;
+        let old_w = grid.width();
+        let old_h = grid.height();
 
         let new_w = if self.swap_xy { old_h } else { old_w };
         let new_h = if self.swap_xy { old_w } else { old_h };
 
-        let mut result = vec![' '; new_w * new_h];
+        let mut result = Grid::new(new_w, new_h, b'.');
 
         for y in 0..old_h {
             for x in 0..old_w {
@@ -218,69 +198,84 @@ impl Transformation {
 
                 let (final_x, final_y) = if self.swap_xy { (cy, cx) } else { (cx, cy) };
 
-                let old_idx = y * old_w + x;
-                let new_idx = final_y * new_w + final_x;
-
-                result[new_idx] = grid.data[old_idx];
+                result.set(final_x, final_y, grid.get(x, y).unwrap());
             }
         }
 
-        Grid {
-            data: result,
-            width: new_w,
-            height: new_h,
-        }
+        result
     }
 }
 
 const TRANSFORMATIONS: [Transformation; 8] = [
     Transformation {
+        name: "R0",
         swap_xy: false,
         invert_x: false,
         invert_y: false,
-    }, // R0
+    },
     Transformation {
+        name: "R90",
         swap_xy: true,
         invert_x: false,
         invert_y: true,
-    }, // R90
+    },
     Transformation {
+        name: "R180",
         swap_xy: false,
         invert_x: true,
         invert_y: true,
-    }, // R180
+    },
     Transformation {
+        name: "R270",
         swap_xy: true,
         invert_x: true,
         invert_y: false,
-    }, // R270
+    },
     Transformation {
+        name: "HFlip",
         swap_xy: false,
         invert_x: true,
         invert_y: false,
-    }, // HFlip
+    },
     Transformation {
+        name: "R90H",
         swap_xy: true,
         invert_x: true,
         invert_y: true,
-    }, // R90H
+    },
     Transformation {
+        name: "VFlip",
         swap_xy: false,
         invert_x: false,
         invert_y: true,
-    }, // VFlip (R180H)
+    },
     Transformation {
+        name: "R270H",
         swap_xy: true,
         invert_x: false,
         invert_y: false,
-    }, // R270H
+    },
 ];
 
 struct Variant {
-    grid: Grid,
+    /// Index of the first of [`TRANSFORMATIONS`] giving this variant.
+    transformation: usize,
+    width: usize,
+    height: usize,
     filled: Vec<(usize, usize)>,
 }
 
+impl Variant {
+    fn new(grid: &Grid, transformation: usize) -> Self {
+        Variant {
+            transformation,
+            width: grid.width(),
+            height: grid.height(),
+            filled: grid.positions(b'#').collect(),
+        }
+    }
+}
+
 struct Transformer {
     variants: Vec<Vec<Variant>>,
     filled_counts: Vec<usize>,
@@ -292,19 +287,16 @@ impl Transformer {
         let mut filled_counts = Vec::with_capacity(shapes.len());
 
         for shape in shapes {
-            filled_counts.push(shape.filled_cells().len());
+            filled_counts.push(shape.positions(b'#').count());
             let mut seen = HashSet::new();
             let mut list = Vec::new();
 
-            for transformation in &TRANSFORMATIONS {
+            for (i, transformation) in TRANSFORMATIONS.iter().enumerate() {
                 let transformed = transformation.apply(shape);
 
-                if seen.insert(transformed.clone()) {
-                    let filled = transformed.filled_cells();
-                    list.push(Variant {
-                        grid: transformed,
-                        filled,
-                    });
+                if !seen.contains(&transformed) {
+                    list.push(Variant::new(&transformed, i));
+                    seen.insert(transformed);
                 }
             }
 
@@ -317,6 +309,10 @@ impl Transformer {
         }
     }
 
+    fn shapes(&self) -> usize {
+        self.variants.len()
+    }
+
     fn variants(&self, shape_id: usize) -> &[Variant] {
         &self.variants[shape_id]
     }
@@ -335,142 +331,222 @@ static SHAPE_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(\d+):$").u
 static REGION_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(\d+)x(\d+):(( \d+)+)$").unwrap());
 
 impl Input {
-    fn parse_grid(lines: &mut Lines<BufReader<File>>) -> io::Result<Grid> {
+    fn parse_grid<'a>(lines: &mut impl Iterator<Item = (usize, &'a str)>, mode: Mode) -> io::Result<Grid> {
         let mut rows = Vec::new();
+        let mut last = 0;
 
-        for line_result in lines {
-            let line = line_result?;
-            let trimmed = line.trim();
+        for (i, line) in lines {
+            last = i;
 
-            if trimmed.is_empty() {
+            if line.is_empty() {
                 break;
             }
 
-            rows.push(trimmed.to_string());
+            let mut row = Vec::with_capacity(line.len());
+
+            for c in line.chars() {
+                match c {
+                    '#' => row.push(b'#'),
+                    '.' => row.push(b'.'),
+                    other => {
+                        mode.reject(i, format!("unknown cell '{other}'"))?;
+                        row.push(b'.');
+                    }
+                }
+            }
+
+            rows.push(row);
         }
 
         if rows.is_empty() {
-            return Err(io::Error::new(io::ErrorKind::InvalidData, "Empty grid"));
+            return Err(parse::error(last, "empty grid"));
         }
 
         let width = rows[0].len();
 
         if rows.iter().any(|r| r.len() != width) {
-            return Err(io::Error::new(
-                io::ErrorKind::InvalidData,
-                "All grid rows must have the same length",
-            ));
+            return Err(parse::error(last, "all grid rows must have the same length"));
         }
 
         let height = rows.len();
 
-        let data: Vec<char> = rows
-            .into_iter()
-            .flat_map(|s| s.into_bytes())
-            .map(|b| match b as char {
-                '.' => ' ',
-                other => other,
-            })
-            .collect();
+        if width > 64 || height > 64 {
+            return Err(parse::error(last, "shapes are limited to 64 by 64 cells"));
+        }
+
+        let mut grid = Grid::new(width, height, b'.');
+
+        for (y, row) in rows.iter().enumerate() {
+            for (x, &c) in row.iter().enumerate() {
+                grid.set(x, y, c);
+            }
+        }
 
-        Ok(Grid { data, width, height })
+        Ok(grid)
     }
 
-    fn load(path: &Path) -> io::Result<Self> {
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
-        let mut lines = reader.lines();
+    fn load(path: &Path, mode: Mode) -> io::Result<Self> {
+        let contents = input::read(path)?;
+        let mut lines = contents.lines().enumerate().map(|(i, line)| (i + 1, line));
         let mut shapes: Vec<Grid> = Vec::new();
         let mut regions: Vec<Region> = Vec::new();
 
-        while let Some(line) = lines.next() {
-            let line = line?;
-
-            if let Some(captures) = REGION_REGEX.captures(&line) {
-                let width: usize = captures[1].parse().map_err(|e| {
-                    io::Error::new(
-                        io::ErrorKind::InvalidData,
-                        format!("Invalid region width '{}': {}", &captures[1], e),
-                    )
-                })?;
+        while let Some((i, line)) = lines.next() {
+            if let Some(captures) = REGION_REGEX.captures(line) {
+                let width: usize = captures[1]
+                    .parse()
+                    .map_err(|e| parse::error(i, format!("invalid region width '{}': {e}", &captures[1])))?;
 
-                let height: usize = captures[2].parse().map_err(|e| {
-                    io::Error::new(
-                        io::ErrorKind::InvalidData,
-                        format!("Invalid region height '{}': {}", &captures[1], e),
-                    )
-                })?;
+                let height: usize = captures[2]
+                    .parse()
+                    .map_err(|e| parse::error(i, format!("invalid region height '{}': {e}", &captures[2])))?;
 
                 let shape_quantities: Vec<usize> = captures[3]
                     .split_ascii_whitespace()
                     .map(|s| s.parse())
                     .collect::<Result<_, _>>()
-                    .map_err(|e| {
-                        io::Error::new(io::ErrorKind::InvalidData, format!("Invalid quantity in region: {}", e))
-                    })?;
+                    .map_err(|e| parse::error(i, format!("invalid quantity in region: {e}")))?;
+
+                if shape_quantities.len() > shapes.len() {
+                    mode.reject(
+                        i,
+                        format!(
+                            "region lists {} shapes, only {} defined",
+                            shape_quantities.len(),
+                            shapes.len()
+                        ),
+                    )?;
+                    continue;
+                }
 
                 regions.push(Region {
                     width,
                     height,
                     shape_quantities,
                 });
-            } else {
-                let Some(captures) = SHAPE_REGEX.captures(&line) else {
-                    return Err(io::Error::new(
-                        io::ErrorKind::InvalidData,
-                        format!("Unexpected line format: '{}'", line),
-                    ));
-                };
+            } else if let Some(captures) = SHAPE_REGEX.captures(line) {
+                if !regions.is_empty() {
+                    return Err(parse::error(i, "shape defined after the first region"));
+                }
 
-                let id: usize = captures[1].parse().map_err(|e| {
-                    io::Error::new(
-                        io::ErrorKind::InvalidData,
-                        format!("Invalid shape ID '{}': {}", &captures[1], e),
-                    )
-                })?;
+                let id: usize = captures[1]
+                    .parse()
+                    .map_err(|e| parse::error(i, format!("invalid shape ID '{}': {e}", &captures[1])))?;
 
                 let expected_id = shapes.len();
 
                 if expected_id != id {
-                    return Err(io::Error::new(
-                        io::ErrorKind::InvalidData,
-                        format!(
-                            "Shape ID mismatch: expected {}, got {} (shapes parsed so far)",
-                            expected_id, id
-                        ),
+                    return Err(parse::error(
+                        i,
+                        format!("shape ID mismatch: expected {expected_id}, got {id}"),
                     ));
                 }
 
-                let grid = Input::parse_grid(&mut lines)?;
+                let grid = Input::parse_grid(&mut lines, mode)?;
                 shapes.push(grid);
+            } else if line.is_empty() {
+                mode.reject(i, "blank line")?;
+            } else {
+                mode.reject(i, format!("unexpected line `{line}`"))?;
             }
         }
 
-        return Ok(Input { shapes, regions });
+        Ok(Input { shapes, regions })
     }
 
-    fn count(&self) -> u32 {
-        let mut count = 0;
-        let transformer = Transformer::new(&self.shapes);
+    /// Whether each region can fit its presents.
+    fn fits<'a, B: Board<'a>>(
+        &self,
+        transformer: &'a Transformer,
+        pool: &Pool,
+        budget: &Budget,
+    ) -> Solved<Outcome<Option<Fit>>> {
+        let records: Vec<_> = self.regions.iter().enumerate().collect();
 
-        for region in &self.regions {
+        pool.map(&records, |&(i, region)| {
             let solver = Solver {
                 region,
-                transforms: &transformer,
+                transforms: transformer,
             };
 
-            if solver.can_fit() {
-                count += 1;
+            trace::span(format!("region {}", i + 1), || solver.can_fit::<B>(budget))
+        })
+    }
+}
+
+fn main() -> io::Result<()> {
+    trace::init()?;
+
+    let input = Input::load(Path::new("../input"), Mode::from_args())?;
+    let transformer = Transformer::new(&input.shapes);
+    let pool = Pool::from_args()?;
+    let budget = Budget::from_args()?;
+
+    let fits = if env::args().any(|arg| arg == "--bench") {
+        let start = Instant::now();
+        let baseline = input.fits::<CellBoard>(&transformer, &pool, &budget);
+        let cells = start.elapsed();
+
+        let start = Instant::now();
+        let fits = input.fits::<RowBoard>(&transformer, &pool, &budget);
+        let rows = start.elapsed();
+
+        assert!(
+            baseline
+                .results
+                .iter()
+                .zip(&fits.results)
+                .all(|(a, b)| a.value.as_ref().map(Fit::fits) == b.value.as_ref().map(Fit::fits)),
+            "boards disagree"
+        );
+
+        eprintln!("cell by cell: {cells:?}");
+        eprintln!("row masks:    {rows:?} ({:.1}x)", cells.as_secs_f64() / rows.as_secs_f64());
+
+        fits
+    } else {
+        input.fits::<RowBoard>(&transformer, &pool, &budget)
+    };
+
+    let count = fits.results.iter().filter(|outcome| outcome.value.as_ref().is_some_and(Fit::fits)).count();
+    let mut undecided = 0;
+
+    if env::args().any(|arg| arg == "--witness") {
+        for (i, outcome) in fits.results.iter().enumerate() {
+            if let Some(Fit::Packed(placements)) = &outcome.value {
+                let placements: Vec<String> = placements
+                    .iter()
+                    .map(|p| format!("{}{}@{},{}", p.shape, TRANSFORMATIONS[p.transformation].name, p.x, p.y))
+                    .collect();
+
+                println!("region {}: {}", i + 1, placements.join(" "));
+            }
+        }
+    }
+
+    if env::args().any(|arg| arg == "--verify") {
+        for (i, outcome) in fits.results.iter().enumerate() {
+            if let Some(Fit::Packed(placements)) = &outcome.value {
+                verify::placements(&input.shapes, &input.regions[i], placements)
+                    .map_err(|e| io::Error::other(format!("region {}: {e}", i + 1)))?;
             }
         }
 
-        count
+        eprintln!("verified");
     }
-}
 
-fn main() -> io::Result<()> {
-    let input = Input::load(Path::new("../input"))?;
-    let count = input.count();
+    fits.report();
+
+    for (i, outcome) in fits.results.iter().enumerate() {
+        if let Some(exceeded) = outcome.exceeded {
+            eprintln!("region {}: {exceeded} exceeded after {}", i + 1, outcome.stats);
+            undecided += 1;
+        }
+    }
+
+    if undecided > 0 {
+        return Err(io::Error::other(format!("{undecided} regions undecided, at least {count} fit")));
+    }
 
     println!("{count}");
 
@@ -0,0 +1,46 @@
+//! Checks a packing by drawing every present onto the region, independently of the solver's boards and variants.
+
+use aoc::grid::Grid;
+
+use crate::{Placement, Region, TRANSFORMATIONS};
+
+/// Succeeds if the placements put exactly the region's presents inside it without any two overlapping.
+pub fn placements(shapes: &[Grid], region: &Region, placements: &[Placement]) -> Result<(), String> {
+    let mut occupied = Grid::new(region.width, region.height, b'.');
+    let mut placed = vec![0; shapes.len()];
+
+    for placement in placements {
+        let Placement {
+            shape,
+            transformation,
+            x,
+            y,
+        } = *placement;
+
+        let (Some(grid), Some(transformation)) = (shapes.get(shape), TRANSFORMATIONS.get(transformation)) else {
+            return Err(format!("unknown shape {shape} or transformation {transformation}"));
+        };
+
+        let grid = transformation.apply(grid);
+
+        for (dx, dy) in grid.positions(b'#') {
+            match occupied.get(x + dx, y + dy) {
+                None => return Err(format!("shape {shape} at {x},{y} leaves the region")),
+                Some(b'#') => return Err(format!("shape {shape} at {x},{y} overlaps another present")),
+                Some(_) => occupied.set(x + dx, y + dy, b'#'),
+            }
+        }
+
+        placed[shape] += 1;
+    }
+
+    let mut needed = region.shape_quantities.clone();
+
+    needed.resize(shapes.len(), 0);
+
+    if placed != needed {
+        return Err(format!("placed {placed:?} presents, region needs {needed:?}"));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use std::{env, fmt::Display, io, str::FromStr};
+
+/// Value following `flag` on the command line, if the flag was passed.
+pub fn value<T>(flag: &str) -> io::Result<Option<T>>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let args: Vec<String> = env::args().collect();
+    let Some(i) = args.iter().position(|arg| arg == flag) else {
+        return Ok(None);
+    };
+
+    let Some(value) = args.get(i + 1) else {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("{flag} expects a value")));
+    };
+
+    value
+        .parse()
+        .map(Some)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, format!("{flag} {value}: {e}")))
+}
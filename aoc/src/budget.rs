@@ -0,0 +1,151 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    io,
+    time::{Duration, Instant},
+};
+
+use crate::args;
+
+/// Nodes visited between two looks at the clock.
+const CLOCK_INTERVAL: u64 = 1024;
+
+/// Limits a single solver run may use, every limit being optional.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Budget {
+    pub time: Option<Duration>,
+    pub nodes: Option<u64>,
+    pub memo: Option<usize>,
+}
+
+impl Budget {
+    /// Limits from `--time-limit SECONDS`, `--node-limit N` and `--memo-limit ENTRIES`, unlimited by default.
+    pub fn from_args() -> io::Result<Self> {
+        let time = match args::value::<f64>("--time-limit")? {
+            Some(seconds) => Some(Duration::try_from_secs_f64(seconds).map_err(|e| {
+                io::Error::new(io::ErrorKind::InvalidInput, format!("--time-limit {seconds}: {e}"))
+            })?),
+            None => None,
+        };
+
+        Ok(Budget {
+            time,
+            nodes: args::value("--node-limit")?,
+            memo: args::value("--memo-limit")?,
+        })
+    }
+
+    /// Starts metering a run, the clock starting now.
+    pub fn start(&self) -> Meter {
+        Meter {
+            budget: *self,
+            start: Instant::now(),
+            stats: Stats::default(),
+            exceeded: None,
+        }
+    }
+}
+
+/// The limit a run ran into.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Exceeded {
+    Time,
+    Nodes,
+    Memo,
+}
+
+impl Display for Exceeded {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Exceeded::Time => write!(f, "time limit"),
+            Exceeded::Nodes => write!(f, "node limit"),
+            Exceeded::Memo => write!(f, "memo limit"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Stats {
+    pub nodes: u64,
+    pub memo: usize,
+    pub elapsed: Duration,
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} nodes, {} memo entries, {:?}", self.nodes, self.memo, self.elapsed)
+    }
+}
+
+/// Tracks a run against its [`Budget`]. Solvers call [`Meter::tick`] for every node they visit and give up as soon
+/// as it returns `false`.
+pub struct Meter {
+    budget: Budget,
+    start: Instant,
+    stats: Stats,
+    exceeded: Option<Exceeded>,
+}
+
+impl Meter {
+    /// Counts a visited node, returning whether the run may go on.
+    pub fn tick(&mut self) -> bool {
+        if self.exceeded.is_some() {
+            return false;
+        }
+
+        if self.budget.nodes.is_some_and(|limit| self.stats.nodes >= limit) {
+            self.exceeded = Some(Exceeded::Nodes);
+            return false;
+        }
+
+        self.stats.nodes += 1;
+
+        if self.stats.nodes.is_multiple_of(CLOCK_INTERVAL)
+            && self.budget.time.is_some_and(|limit| self.start.elapsed() > limit)
+        {
+            self.exceeded = Some(Exceeded::Time);
+        }
+
+        self.exceeded.is_none()
+    }
+
+    /// Records the current size of the solver's memo, returning whether the run may go on.
+    pub fn memo(&mut self, entries: usize) -> bool {
+        self.stats.memo = self.stats.memo.max(entries);
+
+        if self.budget.memo.is_some_and(|limit| entries > limit) {
+            self.exceeded.get_or_insert(Exceeded::Memo);
+        }
+
+        self.exceeded.is_none()
+    }
+
+    pub fn exceeded(&self) -> Option<Exceeded> {
+        self.exceeded
+    }
+
+    /// Ends the run with `value`, which is only partial when a limit was hit.
+    pub fn finish<T>(self, value: T) -> Outcome<T> {
+        Outcome {
+            value,
+            exceeded: self.exceeded,
+            stats: Stats {
+                elapsed: self.start.elapsed(),
+                ..self.stats
+            },
+        }
+    }
+}
+
+/// What a metered run produced.
+#[derive(Debug)]
+pub struct Outcome<T> {
+    pub value: T,
+    pub exceeded: Option<Exceeded>,
+    pub stats: Stats,
+}
+
+impl<T> Outcome<T> {
+    pub fn is_complete(&self) -> bool {
+        self.exceeded.is_none()
+    }
+}
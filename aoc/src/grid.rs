@@ -0,0 +1,193 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    io,
+};
+
+use crate::{
+    input::{self, Ragged},
+    parse::{self, Mode},
+};
+
+/// A rectangular grid of ASCII cells, one byte per cell.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid {
+    width: usize,
+    height: usize,
+    cells: Vec<u8>,
+}
+
+impl Grid {
+    pub fn new(width: usize, height: usize, fill: u8) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Parses `text` as a grid, rejecting ragged rows and non-ASCII characters.
+    pub fn parse(text: &str, mode: Mode) -> io::Result<Self> {
+        let rows = input::rows(text, Ragged::Reject, mode)?;
+        let width = rows.first().map_or(0, |row| row.cells.len());
+        let mut cells = Vec::with_capacity(width * rows.len());
+
+        for row in &rows {
+            for &c in &row.cells {
+                if !c.is_ascii() {
+                    return Err(parse::error(row.line, format!("'{c}' is not an ASCII character")));
+                }
+
+                cells.push(c as u8);
+            }
+        }
+
+        Ok(Grid {
+            width,
+            height: rows.len(),
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<u8> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        Some(self.cells[self.width * y + x])
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, value: u8) {
+        if x < self.width && y < self.height {
+            self.cells[self.width * y + x] = value;
+        }
+    }
+
+    /// Coordinates of every cell holding `value`, row by row.
+    pub fn positions(&self, value: u8) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(move |(_, c)| **c == value)
+            .map(|(i, _)| (i % self.width, i / self.width))
+    }
+}
+
+impl Display for Grid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for row in self.cells.chunks(self.width.max(1)) {
+            writeln!(f, "{}", String::from_utf8_lossy(row))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A rectangular grid of booleans, packed 64 cells to a word with every row starting on a new word.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    stride: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    pub fn new(width: usize, height: usize) -> Self {
+        let stride = width.div_ceil(64);
+
+        BitGrid {
+            width,
+            height,
+            stride,
+            words: vec![0; stride * height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<bool> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        Some(self.words[self.stride * y + x / 64] >> (x % 64) & 1 == 1)
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, value: bool) {
+        if x >= self.width || y >= self.height {
+            return;
+        }
+
+        let word = &mut self.words[self.stride * y + x / 64];
+
+        if value {
+            *word |= 1 << (x % 64);
+        } else {
+            *word &= !(1 << (x % 64));
+        }
+    }
+
+    /// Number of words backing each row.
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    /// Whether a shape already shifted to its column fits at row `y` without covering a set cell, `mask` holding
+    /// [`BitGrid::stride`] words per row. Cells past the right edge must not be set in `mask`.
+    pub fn fits_words(&self, y: usize, mask: &[u64]) -> bool {
+        let start = self.stride * y;
+
+        start + mask.len() <= self.words.len()
+            && self.words[start..start + mask.len()].iter().zip(mask).all(|(word, mask)| word & mask == 0)
+    }
+
+    /// Flips the cells covered by a shifted shape, see [`BitGrid::fits_words`], so the same call places and removes it.
+    pub fn toggle_words(&mut self, y: usize, mask: &[u64]) {
+        let start = self.stride * y;
+
+        for (word, mask) in self.words[start..start + mask.len()].iter_mut().zip(mask) {
+            *word ^= mask;
+        }
+    }
+
+    /// The first clear cell at or after `(x, y)` in row-major order.
+    pub fn first_clear_from(&self, x: usize, y: usize) -> Option<(usize, usize)> {
+        let start = (self.stride * y + x / 64).min(self.words.len());
+        let skipped = (1 << (x % 64)) - 1;
+
+        for (k, &word) in self.words[start..].iter().enumerate() {
+            let word = if k == 0 { word | skipped } else { word };
+
+            if word == u64::MAX {
+                continue;
+            }
+
+            let i = start + k;
+            let x = i % self.stride * 64 + (!word).trailing_zeros() as usize;
+
+            if x < self.width {
+                return Some((x, i / self.stride));
+            }
+        }
+
+        None
+    }
+}
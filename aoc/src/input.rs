@@ -0,0 +1,123 @@
+use std::{fs, io, path::Path};
+
+use crate::parse::{self, Mode};
+#[cfg(feature = "vault")]
+use crate::vault::{self, Key};
+
+/// Reads `path` as text, see [`normalise`]. With the `vault` feature, where only the sealed copy of the file is checked
+/// in, that copy is opened instead, see `vault`. Parts leave it off and are given their input opened by the runner.
+pub fn read(path: &Path) -> io::Result<String> {
+    let text = match fs::read_to_string(path) {
+        #[cfg(feature = "vault")]
+        Err(e) if e.kind() == io::ErrorKind::NotFound && vault::sealed_path(path).is_file() => {
+            vault::open_file(&Key::load()?, path)?
+        }
+        text => text?,
+    };
+
+    Ok(normalise(&text))
+}
+
+/// Drops a leading byte order mark, turns CRLF and lone CR line endings into LF, removes spaces and tabs at the end of
+/// lines and blank lines at the end. Grids that rely on trailing spaces get them back from [`Ragged::Pad`].
+pub fn normalise(text: &str) -> String {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let mut normalised = String::with_capacity(text.len());
+
+    for line in text.replace("\r\n", "\n").replace('\r', "\n").lines() {
+        normalised.push_str(line.trim_end_matches([' ', '\t']));
+        normalised.push('\n');
+    }
+
+    let len = normalised.trim_end_matches('\n').len();
+
+    if len > 0 {
+        normalised.truncate(len + 1);
+    } else {
+        normalised.clear();
+    }
+
+    normalised
+}
+
+/// What [`rows`] does with rows whose width differs from the rest of the grid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Ragged {
+    /// Fail, reporting the first row that is not as wide as the first one.
+    Reject,
+    /// Pad every row with the given character up to the widest row.
+    Pad(char),
+}
+
+/// One row of a grid together with its 1-based line number.
+pub struct Row {
+    pub line: usize,
+    pub cells: Vec<char>,
+}
+
+/// Splits `text` into the rows of a rectangular grid, widths counted in characters.
+pub fn rows(text: &str, ragged: Ragged, mode: Mode) -> io::Result<Vec<Row>> {
+    let mut rows = vec![];
+
+    for (i, line) in text.lines().enumerate() {
+        if line.is_empty() {
+            mode.reject(i + 1, "blank line")?;
+            continue;
+        }
+
+        rows.push(Row {
+            line: i + 1,
+            cells: line.chars().collect(),
+        });
+    }
+
+    match ragged {
+        Ragged::Reject => {
+            let width = rows.first().map_or(0, |row| row.cells.len());
+
+            if let Some((n, row)) = rows.iter().enumerate().find(|(_, row)| row.cells.len() != width) {
+                return Err(parse::error(
+                    row.line,
+                    format!("row {} is {} wide, expected {width}", n + 1, row.cells.len()),
+                ));
+            }
+        }
+        Ragged::Pad(c) => {
+            let width = rows.iter().map(|row| row.cells.len()).max().unwrap_or(0);
+
+            for row in &mut rows {
+                row.cells.resize(width, c);
+            }
+        }
+    }
+
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalises_line_endings_and_trailing_blanks() {
+        assert_eq!(normalise("\u{feff}1\r\n2\r3\n\n\n"), "1\n2\n3\n");
+        assert_eq!(normalise("1 \n2\t\n 3 \t\n"), "1\n2\n 3\n");
+        assert_eq!(normalise("\n \n"), "");
+    }
+
+    #[test]
+    fn trailing_spaces_do_not_fail_a_strict_parse() {
+        assert_eq!(parse::records::<u32>(&normalise("1 \n2\r\n"), Mode::Strict).unwrap(), [1, 2]);
+    }
+
+    #[test]
+    fn pads_or_rejects_ragged_rows() {
+        let widths = |rows: Vec<Row>| rows.iter().map(|row| row.cells.len()).collect::<Vec<_>>();
+
+        assert_eq!(widths(rows("ab\na\n", Ragged::Pad(' '), Mode::Strict).unwrap()), [2, 2]);
+        assert_eq!(
+            rows("ab\na\n", Ragged::Reject, Mode::Strict).err().map(|e| e.to_string()).as_deref(),
+            Some("line 2: row 2 is 1 wide, expected 2")
+        );
+    }
+}
@@ -0,0 +1,12 @@
+pub mod args;
+pub mod budget;
+pub mod grid;
+pub mod input;
+pub mod memo;
+pub mod parallel;
+pub mod parse;
+pub mod prop;
+pub mod rng;
+pub mod trace;
+#[cfg(feature = "vault")]
+pub mod vault;
@@ -0,0 +1,519 @@
+//! Memo tables for recursive solvers: compact keys, a fast hasher, hit and miss counts and an optional LRU bound.
+
+use std::{
+    collections::HashMap,
+    fmt::{self, Display, Formatter},
+    hash::{BuildHasherDefault, Hash, Hasher},
+    io,
+};
+
+use crate::args;
+
+/// Multiplier of the Fx hash used by rustc, which is much cheaper than SipHash on small keys. It gives up SipHash's
+/// protection against crafted collisions, which puzzle inputs do not need.
+const SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+/// Words a [`Key`] keeps inline before spilling to the heap.
+const INLINE: usize = 4;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FxHasher {
+    hash: u64,
+}
+
+impl FxHasher {
+    fn add(&mut self, word: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(SEED);
+    }
+}
+
+impl Hasher for FxHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+
+        for chunk in &mut chunks {
+            self.add(u64::from_le_bytes(chunk.try_into().unwrap()));
+        }
+
+        for &byte in chunks.remainder() {
+            self.add(u64::from(byte));
+        }
+    }
+
+    fn write_u8(&mut self, i: u8) {
+        self.add(u64::from(i));
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.add(u64::from(i));
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.add(i);
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.add(i as u64);
+    }
+
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+/// Entries a memo may hold from `--memo-capacity N`, unbounded without it.
+pub fn capacity_from_args() -> io::Result<Option<usize>> {
+    args::value("--memo-capacity")
+}
+
+pub type FastMap<K, V> = HashMap<K, V, BuildHasherDefault<FxHasher>>;
+
+/// Small unsigned values packed bit by bit, so a key made of a few counters and a mask fits in a couple of words
+/// instead of a cloned `Vec`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Key {
+    Inline([u64; INLINE]),
+    Spilled(Box<[u64]>),
+}
+
+/// Builds a [`Key`] from values pushed with their width in bits. Keys compared with each other must be built from
+/// the same widths in the same order.
+#[derive(Debug, Default)]
+pub struct KeyBuilder {
+    inline: [u64; INLINE],
+    spilled: Vec<u64>,
+    bits: usize,
+}
+
+impl KeyBuilder {
+    pub fn new() -> Self {
+        KeyBuilder::default()
+    }
+
+    /// Appends the low `width` bits of `value`, `width` being at most 64.
+    pub fn push(&mut self, value: u64, width: u32) -> &mut Self {
+        debug_assert!(width <= 64 && (width == 64 || value >> width == 0), "{value} does not fit in {width} bits");
+
+        if width == 0 {
+            return self;
+        }
+
+        let (word, offset) = (self.bits / 64, self.bits % 64);
+
+        self.or(word, value << offset);
+
+        if offset + width as usize > 64 {
+            self.or(word + 1, value >> (64 - offset));
+        }
+
+        self.bits += width as usize;
+        self
+    }
+
+    fn or(&mut self, word: usize, bits: u64) {
+        if word >= INLINE && self.spilled.is_empty() {
+            self.spilled.extend_from_slice(&self.inline);
+        }
+
+        if self.spilled.is_empty() {
+            self.inline[word] |= bits;
+        } else {
+            if word >= self.spilled.len() {
+                self.spilled.resize(word + 1, 0);
+            }
+
+            self.spilled[word] |= bits;
+        }
+    }
+
+    pub fn finish(self) -> Key {
+        if self.spilled.is_empty() {
+            Key::Inline(self.inline)
+        } else {
+            Key::Spilled(self.spilled.into_boxed_slice())
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Stats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    /// Largest number of entries held at once.
+    pub peak: usize,
+}
+
+impl Stats {
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+
+        if lookups == 0 { 0.0 } else { self.hits as f64 / lookups as f64 }
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses ({:.1}% hit), {} evictions, {} entries at most",
+            self.hits,
+            self.misses,
+            100.0 * self.hit_rate(),
+            self.evictions,
+            self.peak
+        )
+    }
+}
+
+/// Sentinel index ending the recency list.
+const NIL: usize = usize::MAX;
+
+struct Slot<K, V> {
+    key: K,
+    value: V,
+    /// Neighbours in the recency list, the most recently used entry at its head.
+    newer: usize,
+    older: usize,
+}
+
+/// Entries kept in order of use, evicting the least recently used one once `capacity` is reached.
+struct Lru<K, V> {
+    capacity: usize,
+    index: FastMap<K, usize>,
+    slots: Vec<Slot<K, V>>,
+    newest: usize,
+    oldest: usize,
+}
+
+impl<K: Hash + Eq + Clone, V> Lru<K, V> {
+    fn unlink(&mut self, i: usize) {
+        let (newer, older) = (self.slots[i].newer, self.slots[i].older);
+
+        match newer {
+            NIL => self.newest = older,
+            newer => self.slots[newer].older = older,
+        }
+
+        match older {
+            NIL => self.oldest = newer,
+            older => self.slots[older].newer = newer,
+        }
+    }
+
+    fn push_newest(&mut self, i: usize) {
+        self.slots[i].newer = NIL;
+        self.slots[i].older = self.newest;
+
+        if self.newest != NIL {
+            self.slots[self.newest].newer = i;
+        }
+
+        self.newest = i;
+
+        if self.oldest == NIL {
+            self.oldest = i;
+        }
+    }
+
+    fn get(&mut self, key: &K) -> Option<&V> {
+        let i = *self.index.get(key)?;
+
+        self.unlink(i);
+        self.push_newest(i);
+
+        Some(&self.slots[i].value)
+    }
+
+    /// Stores an entry, returning whether another one was evicted to make room.
+    fn insert(&mut self, key: K, value: V) -> bool {
+        if let Some(&i) = self.index.get(&key) {
+            self.slots[i].value = value;
+            self.unlink(i);
+            self.push_newest(i);
+            return false;
+        }
+
+        let slot = Slot {
+            key: key.clone(),
+            value,
+            newer: NIL,
+            older: NIL,
+        };
+
+        let evicted = self.slots.len() >= self.capacity;
+        let i = if evicted {
+            let i = self.oldest;
+
+            self.unlink(i);
+            self.index.remove(&self.slots[i].key);
+            self.slots[i] = slot;
+            i
+        } else {
+            self.slots.push(slot);
+            self.slots.len() - 1
+        };
+
+        self.index.insert(key, i);
+        self.push_newest(i);
+        evicted
+    }
+}
+
+enum Store<K, V> {
+    Unbounded(FastMap<K, V>),
+    Lru(Lru<K, V>),
+}
+
+/// Cache of solved subproblems, counting how often it is hit.
+pub struct Memo<K, V> {
+    store: Store<K, V>,
+    stats: Stats,
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Memo {
+            store: Store::Unbounded(FastMap::default()),
+            stats: Stats::default(),
+        }
+    }
+
+    /// A memo holding at most `capacity` entries, dropping the least recently used ones. Solvers must be able to
+    /// recompute whatever was dropped.
+    pub fn bounded(capacity: usize) -> Self {
+        Memo {
+            store: Store::Lru(Lru {
+                capacity: capacity.max(1),
+                index: FastMap::default(),
+                slots: Vec::new(),
+                newest: NIL,
+                oldest: NIL,
+            }),
+            stats: Stats::default(),
+        }
+    }
+
+    /// Bounded when `capacity` is given.
+    pub fn with_capacity(capacity: Option<usize>) -> Self {
+        capacity.map_or_else(Memo::new, Memo::bounded)
+    }
+
+    pub fn get(&mut self, key: &K) -> Option<V> {
+        let value = match &mut self.store {
+            Store::Unbounded(map) => map.get(key),
+            Store::Lru(lru) => lru.get(key),
+        };
+
+        match value {
+            Some(value) => {
+                self.stats.hits += 1;
+                Some(value.clone())
+            }
+            None => {
+                self.stats.misses += 1;
+                None
+            }
+        }
+    }
+
+    pub fn insert(&mut self, key: K, value: V) {
+        match &mut self.store {
+            Store::Unbounded(map) => {
+                map.insert(key, value);
+            }
+            Store::Lru(lru) => {
+                if lru.insert(key, value) {
+                    self.stats.evictions += 1;
+                }
+            }
+        }
+
+        self.stats.peak = self.stats.peak.max(self.len());
+    }
+
+    pub fn len(&self) -> usize {
+        match &self.store {
+            Store::Unbounded(map) => map.len(),
+            Store::Lru(lru) => lru.index.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Memo::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lru(memo: &Memo<u32, u32>) -> &Lru<u32, u32> {
+        match &memo.store {
+            Store::Lru(lru) => lru,
+            Store::Unbounded(_) => panic!("memo is unbounded"),
+        }
+    }
+
+    #[test]
+    fn evicts_the_least_recently_used_entry() {
+        let mut memo = Memo::bounded(2);
+
+        memo.insert(1, 10);
+        memo.insert(2, 20);
+        assert_eq!(memo.get(&1), Some(10));
+        memo.insert(3, 30);
+
+        // The hit on 1 made 2 the oldest
+        assert_eq!(memo.get(&2), None);
+        assert_eq!(memo.get(&1), Some(10));
+        assert_eq!(memo.get(&3), Some(30));
+
+        // Updating an entry also counts as a use
+        memo.insert(1, 11);
+        memo.insert(4, 40);
+        assert_eq!(memo.get(&3), None);
+        assert_eq!(memo.get(&1), Some(11));
+        assert_eq!(memo.len(), 2);
+    }
+
+    #[test]
+    fn reuses_the_slots_of_evicted_entries() {
+        let mut memo = Memo::bounded(3);
+
+        for i in 0..100 {
+            memo.insert(i, i * 2);
+            assert!(memo.len() <= 3);
+        }
+
+        let lru = lru(&memo);
+
+        assert_eq!(lru.slots.len(), 3);
+        assert_eq!(lru.index.len(), 3);
+        assert!(lru.index.iter().all(|(key, &i)| lru.slots[i].key == *key));
+        assert_eq!((97..100).map(|i| memo.get(&i)).collect::<Vec<_>>(), [Some(194), Some(196), Some(198)]);
+    }
+
+    #[test]
+    fn counts_hits_misses_and_evictions() {
+        let mut memo = Memo::bounded(2);
+
+        assert_eq!(memo.stats().hit_rate(), 0.0);
+        memo.get(&1);
+        memo.insert(1, 10);
+        memo.get(&1);
+        memo.get(&1);
+        memo.insert(2, 20);
+        memo.insert(3, 30);
+        memo.insert(4, 40);
+        memo.get(&2);
+
+        let stats = memo.stats();
+
+        assert_eq!((stats.hits, stats.misses, stats.evictions, stats.peak), (2, 2, 2, 2));
+        assert_eq!(stats.hit_rate(), 0.5);
+        assert_eq!(stats.to_string(), "2 hits, 2 misses (50.0% hit), 2 evictions, 2 entries at most");
+
+        let mut memo = Memo::new();
+
+        (0..5).for_each(|i| memo.insert(i, i));
+        memo.get(&4);
+
+        let stats = memo.stats();
+
+        assert_eq!((stats.hits, stats.misses, stats.evictions, stats.peak), (1, 0, 0, 5));
+    }
+
+    #[test]
+    fn holds_a_single_entry_at_capacity_zero_or_one() {
+        for capacity in [0, 1] {
+            let mut memo = Memo::bounded(capacity);
+
+            memo.insert(1, 10);
+            assert_eq!(memo.get(&1), Some(10));
+            memo.insert(2, 20);
+            assert_eq!(memo.get(&1), None);
+            assert_eq!(memo.get(&2), Some(20));
+            assert_eq!((memo.len(), memo.stats().evictions), (1, 1));
+        }
+
+        assert_eq!(Memo::<u32, u32>::with_capacity(None).len(), 0);
+        assert!(matches!(Memo::<u32, u32>::with_capacity(Some(0)).store, Store::Lru(_)));
+    }
+
+    #[test]
+    fn stops_a_run_past_its_memo_limit() {
+        use crate::budget::{Budget, Exceeded};
+
+        for limit in [0, 1] {
+            let mut meter = Budget { memo: Some(limit), ..Budget::default() }.start();
+            let mut memo = Memo::new();
+
+            let entries = (0..3)
+                .take_while(|&i| {
+                    memo.insert(i, i);
+                    meter.memo(memo.len())
+                })
+                .count();
+
+            assert_eq!(entries, limit);
+            assert_eq!(meter.exceeded(), Some(Exceeded::Memo));
+            assert_eq!(meter.finish(()).stats.memo, limit + 1);
+        }
+    }
+
+    fn key(values: &[(u64, u32)]) -> Key {
+        let mut builder = KeyBuilder::new();
+
+        values.iter().for_each(|&(value, width)| _ = builder.push(value, width));
+        builder.finish()
+    }
+
+    #[test]
+    fn packs_values_across_words() {
+        assert_eq!(key(&[(1, 1), (0b11, 2)]), Key::Inline([0b111, 0, 0, 0]));
+        assert_eq!(key(&[(0, 60), (0xff, 8)]), Key::Inline([0xf << 60, 0xf, 0, 0]));
+        assert_eq!(key(&[(u64::MAX, 64), (0, 0), (1, 1)]), Key::Inline([u64::MAX, 1, 0, 0]));
+        assert_ne!(key(&[(1, 3), (2, 3)]), key(&[(2, 3), (1, 3)]));
+    }
+
+    #[test]
+    fn spills_keys_wider_than_the_inline_words() {
+        let fits = key(&[(u64::MAX, 64); INLINE]);
+
+        assert_eq!(fits, Key::Inline([u64::MAX; INLINE]));
+
+        let values: Vec<(u64, u32)> = (0..100).map(|i| (i % 8, 3)).collect();
+        let spilled = key(&values);
+
+        let Key::Spilled(words) = &spilled else { panic!("{spilled:?} is inline") };
+
+        assert_eq!(words.len(), 5);
+        assert_eq!(words[0] & 0b111_111_111, 0b010_001_000);
+        // Value 21 is split between the first and second words
+        assert_eq!((words[0] >> 63, words[1] & 0b11), (1, 0b10));
+        assert_eq!(words[4] >> (300 - 256 - 3), 3);
+        assert_eq!(spilled, key(&values));
+
+        let mut other = values.clone();
+
+        other[99].0 = 4;
+        assert_ne!(spilled, key(&other));
+
+        let mut memo = Memo::new();
+
+        memo.insert(spilled.clone(), 1);
+        assert_eq!(memo.get(&key(&values)), Some(1));
+        assert_eq!(memo.get(&key(&other)), None);
+    }
+}
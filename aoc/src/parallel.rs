@@ -0,0 +1,137 @@
+use std::{
+    io,
+    num::NonZeroUsize,
+    panic,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::args;
+
+/// Number of records listed by [`Solved::report`].
+const REPORTED: usize = 5;
+
+/// Solves independent records on a number of worker threads.
+#[derive(Clone, Copy, Debug)]
+pub struct Pool {
+    workers: usize,
+    /// Whether the pool was asked for on the command line, in which case timings are reported.
+    requested: bool,
+}
+
+impl Pool {
+    pub fn new(workers: usize) -> Self {
+        Pool {
+            workers: workers.max(1),
+            requested: true,
+        }
+    }
+
+    /// Sequential unless `--jobs N` was passed on the command line, `--jobs 0` using every available core.
+    pub fn from_args() -> io::Result<Self> {
+        let Some(workers) = args::value::<usize>("--jobs")? else {
+            return Ok(Pool {
+                workers: 1,
+                requested: false,
+            });
+        };
+
+        if workers == 0 {
+            return Ok(Pool::new(thread::available_parallelism().map_or(1, NonZeroUsize::get)));
+        }
+
+        Ok(Pool::new(workers))
+    }
+
+    pub fn workers(&self) -> usize {
+        self.workers
+    }
+
+    /// Applies `f` to every record, handing records to workers as they become free. Results keep the order of
+    /// `records` whatever order they finish in.
+    pub fn map<T, R, F>(&self, records: &[T], f: F) -> Solved<R>
+    where
+        T: Sync,
+        R: Send,
+        F: Fn(&T) -> R + Sync,
+    {
+        let next = AtomicUsize::new(0);
+        let work = || {
+            let mut done = vec![];
+
+            loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(record) = records.get(i) else {
+                    return done;
+                };
+
+                let start = Instant::now();
+                let result = f(record);
+
+                done.push((i, result, start.elapsed()));
+            }
+        };
+
+        let done = if self.workers == 1 {
+            work()
+        } else {
+            thread::scope(|scope| {
+                let workers: Vec<_> = (0..self.workers.min(records.len())).map(|_| scope.spawn(work)).collect();
+
+                workers
+                    .into_iter()
+                    .flat_map(|worker| worker.join().unwrap_or_else(|e| panic::resume_unwind(e)))
+                    .collect()
+            })
+        };
+
+        let mut results: Vec<Option<R>> = (0..records.len()).map(|_| None).collect();
+        let mut timings = vec![Duration::ZERO; records.len()];
+
+        for (i, result, elapsed) in done {
+            results[i] = Some(result);
+            timings[i] = elapsed;
+        }
+
+        Solved {
+            results: results.into_iter().map(|result| result.expect("every record is solved")).collect(),
+            timings,
+            report: self.requested,
+        }
+    }
+}
+
+/// Results of [`Pool::map`], in record order, with the time each record took.
+pub struct Solved<R> {
+    pub results: Vec<R>,
+    pub timings: Vec<Duration>,
+    report: bool,
+}
+
+impl<R> Solved<R> {
+    /// Indices of the `n` slowest records, slowest first.
+    pub fn slowest(&self, n: usize) -> Vec<(usize, Duration)> {
+        let mut timings: Vec<_> = self.timings.iter().copied().enumerate().collect();
+
+        timings.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        timings.truncate(n);
+        timings
+    }
+
+    /// Writes the slowest records to stderr when the pool was requested with `--jobs`, so stdout keeps only the
+    /// answer.
+    pub fn report(&self) {
+        if !self.report {
+            return;
+        }
+
+        let total: Duration = self.timings.iter().sum();
+
+        eprintln!("{} records, {total:?} of work", self.timings.len());
+
+        for (i, elapsed) in self.slowest(REPORTED) {
+            eprintln!("  record {}: {elapsed:?}", i + 1);
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use std::{env, fmt::Display, io, str::FromStr};
+
+use crate::warn;
+
+const TARGET: &str = "parse";
+
+/// How parsers treat content they do not recognise.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Mode {
+    /// Fail on malformed, unknown or trailing content, reporting the line number. Content that is well formed but
+    /// unusual, such as a repeated record, is left to the solver.
+    #[default]
+    Strict,
+    /// Skip such content and log what was skipped.
+    Lenient,
+}
+
+impl Mode {
+    /// Strict unless `--lenient` was passed on the command line.
+    pub fn from_args() -> Self {
+        if env::args().any(|arg| arg == "--lenient") {
+            Mode::Lenient
+        } else {
+            Mode::Strict
+        }
+    }
+
+    /// Handles content at 1-based `line` that the parser cannot use.
+    pub fn reject(self, line: usize, reason: impl Display) -> io::Result<()> {
+        match self {
+            Mode::Strict => Err(error(line, reason)),
+            Mode::Lenient => {
+                warn!(TARGET, "line {line}: skipped, {reason}");
+                Ok(())
+            }
+        }
+    }
+}
+
+pub fn error(line: usize, reason: impl Display) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("line {line}: {reason}"))
+}
+
+/// Parses every line of `text` as one record.
+pub fn records<T>(text: &str, mode: Mode) -> io::Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let mut records = vec![];
+
+    for (i, line) in text.lines().enumerate() {
+        if line.is_empty() {
+            mode.reject(i + 1, "blank line")?;
+            continue;
+        }
+
+        match line.parse() {
+            Ok(record) => records.push(record),
+            Err(e) => mode.reject(i + 1, format!("{e} in `{line}`"))?,
+        }
+    }
+
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strict_records_fail_on_the_first_malformed_line() {
+        let error = records::<u32>("1\n2\nx\n", Mode::Strict).unwrap_err();
+
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(error.to_string(), "line 3: invalid digit found in string in `x`");
+        assert_eq!(records::<u32>("1\n\n2\n", Mode::Strict).unwrap_err().to_string(), "line 2: blank line");
+    }
+
+    #[test]
+    fn strict_records_take_repeats() {
+        assert_eq!(records::<u32>("1\n1\n", Mode::Strict).unwrap(), [1, 1]);
+    }
+
+    #[test]
+    fn lenient_records_skip_malformed_lines() {
+        assert_eq!(records::<u32>("1\nx\n\n2\n", Mode::Lenient).unwrap(), [1, 2]);
+    }
+}
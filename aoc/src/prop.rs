@@ -0,0 +1,180 @@
+//! Property checks: random cases from a seeded [`Rng`], and for the first case breaking the property the smallest
+//! one shrinking it reaches, so a failure comes with an input small enough to debug by hand.
+
+use std::{
+    any::Any,
+    io,
+    panic::{self, AssertUnwindSafe},
+};
+
+use crate::{args, rng::Rng};
+
+/// Shrinking stops after this many steps, in case a shrinker keeps offering cases no smaller than the last.
+const MAX_STEPS: usize = 10_000;
+
+#[derive(Clone, Debug)]
+pub struct Failure<T> {
+    /// Index of the generated case that failed.
+    pub case: usize,
+    pub original: T,
+    /// The smallest failing case shrinking reached, and why it fails.
+    pub shrunk: T,
+    pub reason: String,
+    pub steps: usize,
+}
+
+/// Number of cases to check: `--cases N`, or else `default`.
+pub fn cases_from_args(default: usize) -> io::Result<usize> {
+    Ok(args::value("--cases")?.unwrap_or(default))
+}
+
+fn panic_reason(payload: Box<dyn Any + Send>) -> String {
+    let message = match (payload.downcast_ref::<&str>(), payload.downcast_ref::<String>()) {
+        (Some(message), _) => message.to_string(),
+        (_, Some(message)) => message.clone(),
+        _ => "unknown".to_owned(),
+    };
+
+    format!("panicked: {message}")
+}
+
+/// Checks `property` on `cases` cases from `generate`. A case panicking fails it too; panic messages are not
+/// printed meanwhile, since shrinking can panic many times over.
+///
+/// The first failing case is shrunk by trying the smaller cases `shrink` offers, in its order, and moving to the
+/// first that still fails until none does.
+pub fn check<T: Clone>(
+    rng: &mut Rng,
+    cases: usize,
+    mut generate: impl FnMut(&mut Rng) -> T,
+    shrink: impl Fn(&T) -> Vec<T>,
+    mut property: impl FnMut(&T) -> Result<(), String>,
+) -> Result<(), Failure<T>> {
+    let hook = panic::take_hook();
+
+    panic::set_hook(Box::new(|_| {}));
+
+    let mut test = |case: &T| match panic::catch_unwind(AssertUnwindSafe(|| property(case))) {
+        Ok(result) => result,
+        Err(payload) => Err(panic_reason(payload)),
+    };
+
+    let mut failure = None;
+
+    for case in 0..cases {
+        let original = generate(rng);
+
+        if let Err(reason) = test(&original) {
+            failure = Some(Failure {
+                case,
+                shrunk: original.clone(),
+                original,
+                reason,
+                steps: 0,
+            });
+            break;
+        }
+    }
+
+    if let Some(failure) = &mut failure {
+        'shrink: while failure.steps < MAX_STEPS {
+            for smaller in shrink(&failure.shrunk) {
+                if let Err(reason) = test(&smaller) {
+                    failure.shrunk = smaller;
+                    failure.reason = reason;
+                    failure.steps += 1;
+                    continue 'shrink;
+                }
+            }
+
+            break;
+        }
+    }
+
+    panic::set_hook(hook);
+
+    match failure {
+        Some(failure) => Err(failure),
+        None => Ok(()),
+    }
+}
+
+/// Shorter lists: halves removed, then quarters and so on down to single items.
+pub fn shrink_list<T: Clone>(items: &[T]) -> Vec<Vec<T>> {
+    let mut smaller = Vec::new();
+    let mut chunk = items.len().div_ceil(2);
+
+    while chunk > 0 {
+        for start in (0..items.len()).step_by(chunk) {
+            let end = (start + chunk).min(items.len());
+
+            smaller.push([&items[..start], &items[end..]].concat());
+        }
+
+        chunk /= 2;
+    }
+
+    smaller
+}
+
+/// Lists with one item shrunk by `shrink`.
+pub fn shrink_items<T: Clone>(items: &[T], shrink: impl Fn(&T) -> Vec<T>) -> Vec<Vec<T>> {
+    let mut smaller = Vec::new();
+
+    for (i, item) in items.iter().enumerate() {
+        for item in shrink(item) {
+            let mut items = items.to_vec();
+
+            items[i] = item;
+            smaller.push(items);
+        }
+    }
+
+    smaller
+}
+
+/// Smaller numbers, the closest to zero first.
+pub fn shrink_number(n: u64) -> Vec<u64> {
+    let mut smaller = vec![0, n / 2, n.saturating_sub(1)];
+
+    smaller.dedup();
+    smaller.retain(|&m| m < n);
+    smaller
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbers(rng: &mut Rng) -> Vec<u64> {
+        (0..rng.between(1, 10)).map(|_| rng.below(20)).collect()
+    }
+
+    fn shrink(list: &[u64]) -> Vec<Vec<u64>> {
+        [shrink_list(list), shrink_items(list, |&n| shrink_number(n))].concat()
+    }
+
+    #[test]
+    fn failures_shrink_to_a_minimal_case() {
+        let failure = check(&mut Rng::new(1), 100, numbers, |list| shrink(list), |list| {
+            if list.iter().sum::<u64>() < 10 { Ok(()) } else { Err("too large".to_owned()) }
+        });
+
+        assert_eq!(failure.map_err(|failure| failure.shrunk), Err(vec![10]));
+    }
+
+    #[test]
+    fn panics_fail_the_case() {
+        let failure = check(&mut Rng::new(1), 100, numbers, |list| shrink(list), |list| {
+            assert!(!list.contains(&7), "seven");
+            Ok(())
+        });
+
+        assert!(failure.is_err_and(|failure| failure.shrunk == [7] && failure.reason.contains("seven")));
+    }
+
+    #[test]
+    fn holding_properties_pass() {
+        assert!(check(&mut Rng::new(1), 100, numbers, |list| shrink(list), |_| Ok(())).is_ok());
+    }
+}
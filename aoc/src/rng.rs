@@ -0,0 +1,92 @@
+//! A small seeded random number generator, so that generated inputs and randomised checks can be reproduced from
+//! the seed alone. SplitMix64: fast, tiny and good enough for anything but cryptography.
+
+use std::{
+    io,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::args;
+
+#[derive(Clone, Debug)]
+pub struct Rng {
+    seed: u64,
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { seed, state: seed }
+    }
+
+    /// Seeded with `--seed N`, or else from the clock. Either way [`Rng::seed`] tells how to get the same numbers
+    /// again.
+    pub fn from_args() -> io::Result<Self> {
+        let seed = match args::value::<u64>("--seed")? {
+            Some(seed) => seed,
+            None => SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos() as u64,
+        };
+
+        Ok(Rng::new(seed))
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.state;
+
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `0..n`, `n` being positive.
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "empty range");
+
+        // Multiplying instead of taking the remainder, and rejecting the few values which would favour low results
+        let threshold = n.wrapping_neg() % n;
+
+        loop {
+            let product = u128::from(self.next_u64()) * u128::from(n);
+
+            if product as u64 >= threshold {
+                return (product >> 64) as u64;
+            }
+        }
+    }
+
+    /// Uniform in `low..=high`.
+    pub fn between(&mut self, low: u64, high: u64) -> u64 {
+        assert!(low <= high, "empty range");
+
+        match (high - low).checked_add(1) {
+            Some(n) => low + self.below(n),
+            None => self.next_u64(),
+        }
+    }
+
+    /// Uniform in `0..n`, for indices.
+    pub fn index(&mut self, n: usize) -> usize {
+        self.below(n as u64) as usize
+    }
+
+    /// True with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64) < p * (1u64 << 53) as f64
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.index(i + 1));
+        }
+    }
+}
@@ -0,0 +1,179 @@
+//! Levelled diagnostics for solver internals. Events go to stderr, so stdout carries only the answer. Warnings and
+//! errors are shown by default, everything else is off unless `--trace` enables it for its target.
+
+use std::{
+    cell::RefCell,
+    fmt::{self, Display, Formatter},
+    io::{self, Write},
+    str::FromStr,
+    sync::OnceLock,
+};
+
+use crate::args;
+
+static FILTER: OnceLock<Filter> = OnceLock::new();
+
+thread_local! {
+    static SPAN: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "error" => Ok(Level::Error),
+            "warn" => Ok(Level::Warn),
+            "info" => Ok(Level::Info),
+            "debug" => Ok(Level::Debug),
+            "trace" => Ok(Level::Trace),
+            _ => Err(format!("unknown level '{s}'")),
+        }
+    }
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Level::Error => "error",
+            Level::Warn => "warn",
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        };
+
+        f.pad(name)
+    }
+}
+
+/// Which targets are traced and how verbosely, written as comma separated `target=level` directives. A bare level
+/// applies to every target without a directive of its own.
+#[derive(Clone, Debug, Default)]
+pub struct Filter {
+    default: Option<Level>,
+    targets: Vec<(String, Level)>,
+}
+
+impl Filter {
+    pub fn enabled(&self, target: &str, level: Level) -> bool {
+        let limit = self
+            .targets
+            .iter()
+            .rev()
+            .find(|(name, _)| name == target)
+            .map(|&(_, limit)| limit)
+            .or(self.default);
+
+        limit.is_some_and(|limit| level <= limit)
+    }
+}
+
+impl FromStr for Filter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut filter = Filter::default();
+
+        for directive in s.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            match directive.split_once('=') {
+                Some((target, level)) => filter.targets.push((target.to_owned(), level.parse()?)),
+                None => filter.default = Some(directive.parse()?),
+            }
+        }
+
+        Ok(filter)
+    }
+}
+
+/// Enables the targets given by `--trace`, only warnings and errors being shown without it.
+pub fn init() -> io::Result<()> {
+    let filter = args::value::<Filter>("--trace")?.unwrap_or(Filter {
+        default: Some(Level::Warn),
+        targets: Vec::new(),
+    });
+
+    // A second call keeps the first filter
+    let _ = FILTER.set(filter);
+
+    Ok(())
+}
+
+pub fn enabled(target: &str, level: Level) -> bool {
+    match FILTER.get() {
+        Some(filter) => filter.enabled(target, level),
+        // Parsers may warn in binaries that never call init
+        None => level <= Level::Warn,
+    }
+}
+
+/// Runs `f` with `label` prefixed to the events it emits on this thread, telling apart records solved in parallel.
+pub fn span<R>(label: impl Display, f: impl FnOnce() -> R) -> R {
+    let previous = SPAN.with_borrow_mut(|span| span.replace(label.to_string()));
+    let result = f();
+
+    SPAN.with_borrow_mut(|span| *span = previous);
+    result
+}
+
+/// Writes an event; use the macros, which skip formatting when the target is off.
+pub fn emit(target: &str, level: Level, args: fmt::Arguments<'_>) {
+    let mut stderr = io::stderr().lock();
+
+    let _ = SPAN.with_borrow(|span| match span {
+        Some(span) => writeln!(stderr, "{level:>5} {target} {span}: {args}"),
+        None => writeln!(stderr, "{level:>5} {target}: {args}"),
+    });
+}
+
+#[macro_export]
+macro_rules! event {
+    ($level:expr, $target:expr, $($arg:tt)+) => {
+        if $crate::trace::enabled($target, $level) {
+            $crate::trace::emit($target, $level, format_args!($($arg)+));
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! error {
+    ($target:expr, $($arg:tt)+) => {
+        $crate::event!($crate::trace::Level::Error, $target, $($arg)+)
+    };
+}
+
+#[macro_export]
+macro_rules! warn {
+    ($target:expr, $($arg:tt)+) => {
+        $crate::event!($crate::trace::Level::Warn, $target, $($arg)+)
+    };
+}
+
+#[macro_export]
+macro_rules! info {
+    ($target:expr, $($arg:tt)+) => {
+        $crate::event!($crate::trace::Level::Info, $target, $($arg)+)
+    };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($target:expr, $($arg:tt)+) => {
+        $crate::event!($crate::trace::Level::Debug, $target, $($arg)+)
+    };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($target:expr, $($arg:tt)+) => {
+        $crate::event!($crate::trace::Level::Trace, $target, $($arg)+)
+    };
+}
@@ -0,0 +1,136 @@
+//! Inputs and answers may not be published, so the repository only holds them sealed: encrypted with
+//! ChaCha20-Poly1305 under a key that never leaves the machines allowed to read them. A sealed file sits next to the
+//! plaintext it replaces, named like it with [`EXTENSION`] appended.
+
+use std::{
+    env,
+    fmt::{self, Debug, Formatter},
+    fs,
+    io,
+    path::{Path, PathBuf},
+};
+
+use chacha20poly1305::{
+    ChaCha20Poly1305,
+    Nonce,
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+};
+
+pub const EXTENSION: &str = "enc";
+
+/// Starts every sealed file, so a file from elsewhere is not mistaken for one.
+const MAGIC: &[u8] = b"aoc-sealed-1\n";
+const NONCE_LEN: usize = 12;
+const KEY_LEN: usize = 32;
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct Key([u8; KEY_LEN]);
+
+impl Debug for Key {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Key(..)")
+    }
+}
+
+impl Key {
+    pub fn generate() -> Self {
+        Key(ChaCha20Poly1305::generate_key(&mut OsRng).into())
+    }
+
+    /// Parses the 64 hex digits a key is written as.
+    pub fn from_hex(hex: &str) -> io::Result<Self> {
+        let hex = hex.trim();
+        let invalid = || io::Error::new(io::ErrorKind::InvalidData, format!("a key is {} hex digits", KEY_LEN * 2));
+
+        if hex.len() != KEY_LEN * 2 || !hex.is_ascii() {
+            return Err(invalid());
+        }
+
+        let mut key = [0; KEY_LEN];
+
+        for (i, byte) in key.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).map_err(|_| invalid())?;
+        }
+
+        Ok(Key(key))
+    }
+
+    pub fn to_hex(&self) -> String {
+        self.0.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+
+    /// Where the key is kept: the file at `AOC_INPUT_KEY_FILE`, by default `~/.config/aoc/input.key`.
+    pub fn path() -> Option<PathBuf> {
+        if let Some(path) = env::var_os("AOC_INPUT_KEY_FILE") {
+            return Some(PathBuf::from(path));
+        }
+
+        env::var_os("HOME").map(|home| PathBuf::from(home).join(".config/aoc/input.key"))
+    }
+
+    /// The key in `AOC_INPUT_KEY`, which is how CI gets it, or else the one in the key file.
+    pub fn load() -> io::Result<Self> {
+        if let Ok(hex) = env::var("AOC_INPUT_KEY") {
+            return Key::from_hex(&hex).map_err(|e| io::Error::new(e.kind(), format!("AOC_INPUT_KEY: {e}")));
+        }
+
+        let path = Key::path().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, "no key, set AOC_INPUT_KEY or AOC_INPUT_KEY_FILE")
+        })?;
+
+        Key::read(&path)
+    }
+
+    /// The key in the file at `path`.
+    pub fn read(path: &Path) -> io::Result<Self> {
+        let context = |e: io::Error| io::Error::new(e.kind(), format!("{}: {e}", path.display()));
+
+        Key::from_hex(&fs::read_to_string(path).map_err(context)?).map_err(context)
+    }
+
+    fn cipher(&self) -> ChaCha20Poly1305 {
+        ChaCha20Poly1305::new(&self.0.into())
+    }
+}
+
+/// Encrypts `plaintext` under a fresh nonce.
+pub fn seal(key: &Key, plaintext: &[u8]) -> Vec<u8> {
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = key.cipher().encrypt(&nonce, plaintext).expect("plaintexts fit in memory, so they fit the cipher");
+
+    [MAGIC, nonce.as_slice(), &ciphertext].concat()
+}
+
+/// Decrypts what [`seal`] made, failing on any other key or on any change to it.
+pub fn open(key: &Key, sealed: &[u8]) -> io::Result<Vec<u8>> {
+    let invalid = |reason| io::Error::new(io::ErrorKind::InvalidData, reason);
+    let sealed = sealed.strip_prefix(MAGIC).ok_or_else(|| invalid("not a sealed file"))?;
+
+    if sealed.len() < NONCE_LEN {
+        return Err(invalid("sealed file is truncated"));
+    }
+
+    let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+
+    key.cipher()
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| invalid("cannot be opened with this key, or was altered"))
+}
+
+/// Where the sealed copy of `path` goes.
+pub fn sealed_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_owned();
+
+    name.push(".");
+    name.push(EXTENSION);
+    path.with_file_name(name)
+}
+
+/// Decrypts the sealed copy of `path`, as text.
+pub fn open_file(key: &Key, path: &Path) -> io::Result<String> {
+    let sealed = sealed_path(path);
+    let context = |e: io::Error| io::Error::new(e.kind(), format!("{}: {e}", sealed.display()));
+    let plaintext = open(key, &fs::read(&sealed).map_err(context)?).map_err(context)?;
+
+    String::from_utf8(plaintext).map_err(|_| context(io::Error::new(io::ErrorKind::InvalidData, "not text")))
+}
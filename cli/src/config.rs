@@ -0,0 +1,116 @@
+use std::{
+    env,
+    fs,
+    io,
+    path::{Path, PathBuf},
+};
+
+/// Site the puzzles and inputs come from.
+const BASE_URL: &str = "https://adventofcode.com";
+
+/// Settings of the `aoc` command. Environment variables win over the config file, which holds `key = value` lines.
+#[derive(Clone, Debug)]
+pub struct Config {
+    /// Value of the site's `session` cookie, copied from a logged in browser.
+    pub session: Option<String>,
+    /// Who to contact about the requests, sent in the User-Agent as the site asks of automated tools.
+    pub contact: Option<String>,
+    pub base_url: String,
+}
+
+impl Config {
+    /// Settings from `AOC_SESSION`, `AOC_CONTACT` and `AOC_BASE_URL`, falling back to `session`, `contact` and
+    /// `base_url` in the file at `AOC_CONFIG`, by default `~/.config/aoc/config`.
+    pub fn load() -> io::Result<Self> {
+        let mut config = Config {
+            session: None,
+            contact: None,
+            base_url: BASE_URL.to_owned(),
+        };
+
+        if let Some(path) = Config::path() {
+            match fs::read_to_string(&path) {
+                Ok(text) => config.read(&text, &path)?,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => return Err(io::Error::new(e.kind(), format!("{}: {e}", path.display()))),
+            }
+        }
+
+        if let Ok(session) = env::var("AOC_SESSION") {
+            config.session = Some(session);
+        }
+
+        if let Ok(contact) = env::var("AOC_CONTACT") {
+            config.contact = Some(contact);
+        }
+
+        if let Ok(base_url) = env::var("AOC_BASE_URL") {
+            config.base_url = base_url;
+        }
+
+        config.session = config.session.map(|s| s.trim().to_owned()).filter(|s| !s.is_empty());
+        config.base_url = config.base_url.trim_end_matches('/').to_owned();
+
+        Ok(config)
+    }
+
+    fn path() -> Option<PathBuf> {
+        if let Some(path) = env::var_os("AOC_CONFIG") {
+            return Some(PathBuf::from(path));
+        }
+
+        env::var_os("HOME").map(|home| PathBuf::from(home).join(".config/aoc/config"))
+    }
+
+    fn read(&mut self, text: &str, path: &Path) -> io::Result<()> {
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}:{}: expected `key = value`", path.display(), i + 1),
+                ));
+            };
+
+            let value = value.trim().to_owned();
+
+            match key.trim() {
+                "session" => self.session = Some(value),
+                "contact" => self.contact = Some(value),
+                "base_url" => self.base_url = value,
+                other => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("{}:{}: unknown setting '{other}'", path.display(), i + 1),
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The session token, which every request for personal data needs.
+    pub fn session(&self) -> io::Result<&str> {
+        self.session.as_deref().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                "no session token, set AOC_SESSION or `session = ...` in ~/.config/aoc/config",
+            )
+        })
+    }
+
+    pub fn user_agent(&self) -> String {
+        let agent = format!("aoc-cli/{}", env!("CARGO_PKG_VERSION"));
+
+        match &self.contact {
+            Some(contact) => format!("{agent} (+{contact})"),
+            None => agent,
+        }
+    }
+}
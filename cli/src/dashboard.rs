@@ -0,0 +1,383 @@
+//! A terminal overview of the registered days: whether each part is solved, what it printed when last run, whether
+//! that is the accepted answer and was verified, and how long it took. A part is timed on a plain run, then run again
+//! with tracing on and verifying its answer where it can, so neither weighs on the time shown. Runs are recorded in
+//! the cache, so the overview outlives the session that ran them.
+
+use std::{
+    collections::HashMap,
+    fs,
+    io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use ratatui::{
+    DefaultTerminal,
+    Frame,
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Paragraph, Row as TableRow, Table, TableState, Wrap},
+};
+
+use crate::{
+    fetch,
+    paths,
+    runner::{self, PARTS},
+    submit::{self, History},
+};
+
+/// Level of the events shown for the days that trace.
+const TRACE: &str = "debug";
+
+/// Module of the parts that check their answer against a witness when run with `--verify`, failing when the two
+/// disagree.
+const VERIFY_MODULE: &str = "src/verify.rs";
+
+const HEADER: [&str; 7] = ["day", "part", "status", "last answer", "check", "verified", "time"];
+const WIDTHS: [Constraint; 7] = [
+    Constraint::Length(7),
+    Constraint::Length(4),
+    Constraint::Length(8),
+    Constraint::Fill(1),
+    Constraint::Length(6),
+    Constraint::Length(8),
+    Constraint::Length(9),
+];
+
+const HELP: &str = "up/down select, enter run, page up/down scroll, q quit";
+
+/// What a part printed when it was last run, and how long that took.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LastRun {
+    /// Seconds since the Unix epoch.
+    pub time: u64,
+    pub elapsed: Duration,
+    /// `None` when the part failed.
+    pub answer: Option<String>,
+    /// Whether the part checked its answer against its witness, see [`verifies`].
+    pub verified: bool,
+}
+
+impl LastRun {
+    pub fn path(cache: &Path, year: u32, day: u32, part: &str) -> PathBuf {
+        cache.join("runs").join(year.to_string()).join(format!("{day:02}-{part}.txt"))
+    }
+
+    /// The part's last run, stored as a `time microseconds ok answer` line, `verified` in place of `ok` when the
+    /// answer was verified, or a `time microseconds failed` line.
+    pub fn load(path: &Path) -> io::Result<Option<Self>> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e),
+        };
+
+        let invalid = || io::Error::new(io::ErrorKind::InvalidData, format!("{}: invalid run", path.display()));
+        let mut fields = text.trim_end().splitn(4, ' ');
+        let time = fields.next().and_then(|t| t.parse().ok()).ok_or_else(invalid)?;
+        let micros = fields.next().and_then(|t| t.parse().ok()).ok_or_else(invalid)?;
+        let (answer, verified) = match (fields.next(), fields.next()) {
+            (Some("ok"), Some(answer)) => (Some(answer.to_owned()), false),
+            (Some("verified"), Some(answer)) => (Some(answer.to_owned()), true),
+            (Some("failed"), None) => (None, false),
+            _ => return Err(invalid()),
+        };
+
+        Ok(Some(LastRun {
+            time,
+            elapsed: Duration::from_micros(micros),
+            answer,
+            verified,
+        }))
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let outcome = match &self.answer {
+            Some(answer) if self.verified => format!("verified {answer}"),
+            Some(answer) => format!("ok {answer}"),
+            None => "failed".to_owned(),
+        };
+
+        fetch::write_atomically(path, format!("{} {} {outcome}\n", self.time, self.elapsed.as_micros()))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// Registered, but the part has no crate yet.
+    Missing,
+    Unsolved,
+    /// Holds the answer the site judged correct, from the submissions or the stored answers.
+    Solved(String),
+}
+
+/// How the last answer compares to the accepted one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Check {
+    /// Not run yet, or not solved, so there is nothing to compare to.
+    Unchecked,
+    Matches,
+    Differs,
+    /// The last run failed.
+    Failed,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Row {
+    pub year: u32,
+    pub day: u32,
+    pub part: &'static str,
+    pub status: Status,
+    pub last: Option<LastRun>,
+}
+
+impl Row {
+    pub fn check(&self) -> Check {
+        match (&self.status, &self.last) {
+            (_, Some(LastRun { answer: None, .. })) => Check::Failed,
+            (Status::Solved(correct), Some(LastRun { answer: Some(answer), .. })) if answer == correct => {
+                Check::Matches
+            }
+            (Status::Solved(_), Some(_)) => Check::Differs,
+            _ => Check::Unchecked,
+        }
+    }
+
+    /// The row's text, column by column.
+    pub fn cells(&self) -> [String; 7] {
+        let status = match self.status {
+            Status::Missing => "missing",
+            Status::Unsolved => "unsolved",
+            Status::Solved(_) => "solved",
+        };
+
+        let check = match self.check() {
+            Check::Unchecked => "",
+            Check::Matches => "ok",
+            Check::Differs => "WRONG",
+            Check::Failed => "failed",
+        };
+
+        let last = self.last.as_ref();
+
+        [
+            format!("{}/{:02}", self.year, self.day),
+            self.part.to_owned(),
+            status.to_owned(),
+            last.and_then(|last| last.answer.clone()).unwrap_or_default(),
+            check.to_owned(),
+            if last.is_some_and(|last| last.verified) { "yes" } else { "" }.to_owned(),
+            last.map(|last| runner::format_elapsed(last.elapsed)).unwrap_or_default(),
+        ]
+    }
+}
+
+/// A row per part of every registered day.
+pub fn rows(root: &Path) -> io::Result<Vec<Row>> {
+    let cache = paths::cache(root);
+    let mut rows = Vec::new();
+
+    for (year, day) in runner::registered(root)? {
+        let day_dir = paths::day(root, year, day);
+        // Answers submitted from another checkout are only known from the stored ones
+        let stored = runner::stored_answers(&day_dir)?.unwrap_or_default();
+
+        for (i, part) in PARTS.into_iter().enumerate() {
+            let status = if !day_dir.join(part).join("Cargo.toml").is_file() {
+                Status::Missing
+            } else {
+                let history = History::load(History::path(&cache, year, day, i as u32 + 1))?;
+
+                match history.correct().map(str::to_owned).or_else(|| stored[i].clone()) {
+                    Some(correct) => Status::Solved(correct),
+                    None => Status::Unsolved,
+                }
+            };
+
+            rows.push(Row {
+                year,
+                day,
+                part,
+                status,
+                last: LastRun::load(&LastRun::path(&cache, year, day, part))?,
+            });
+        }
+    }
+
+    Ok(rows)
+}
+
+/// Whether the part in `part_dir` verifies its answer when run with `--verify`, the parts that do keeping the check
+/// in a `verify` module.
+pub fn verifies(part_dir: &Path) -> bool {
+    part_dir.join(VERIFY_MODULE).is_file()
+}
+
+/// The two runs of a part: a plain one, which times it, and one with tracing on, verifying its answer if it can.
+pub struct Runs {
+    pub timed: runner::Timed,
+    pub checked: runner::Timed,
+}
+
+/// Runs a row's part on its input, timed on its own and then again to trace and verify it, and records the runs.
+pub fn run(root: &Path, row: &mut Row) -> io::Result<Runs> {
+    if row.status == Status::Missing {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{}/{:02} has no part {} yet", row.year, row.day, row.part),
+        ));
+    }
+
+    let part_dir = paths::day(root, row.year, row.day).join(row.part);
+    let verify = verifies(&part_dir);
+    let timed = runner::timed(&part_dir, &[])?;
+    let args: &[&str] = if verify { &["--trace", TRACE, "--verify"] } else { &["--trace", TRACE] };
+    let checked = runner::timed(&part_dir, args)?;
+    let answer = timed.answer().map(str::to_owned);
+    let last = LastRun {
+        time: submit::now(),
+        // As precise as it is stored
+        elapsed: Duration::from_micros(timed.elapsed.as_micros() as u64),
+        // A part fails when its witness disagrees
+        verified: verify && answer.is_some() && checked.answer() == answer.as_deref(),
+        answer,
+    };
+
+    last.save(&LastRun::path(&paths::cache(root), row.year, row.day, row.part))?;
+    row.last = Some(last);
+
+    Ok(Runs { timed, checked })
+}
+
+/// The dashboard's state between two frames.
+pub struct Dashboard {
+    root: PathBuf,
+    rows: Vec<Row>,
+    state: TableState,
+    /// What each row printed on its timed run and traced on its checked one, when run in this session.
+    outputs: HashMap<usize, (String, String)>,
+    scroll: u16,
+    message: String,
+}
+
+impl Dashboard {
+    pub fn new(root: &Path) -> io::Result<Self> {
+        let rows = rows(root)?;
+
+        Ok(Dashboard {
+            root: root.to_path_buf(),
+            state: TableState::default().with_selected((!rows.is_empty()).then_some(0)),
+            rows,
+            outputs: HashMap::new(),
+            scroll: 0,
+            message: HELP.to_owned(),
+        })
+    }
+
+    pub fn select(&mut self, i: usize) {
+        self.state.select(Some(i.min(self.rows.len().saturating_sub(1))));
+        self.scroll = 0;
+    }
+
+    /// Runs the selected row, keeping what it printed for the output panes.
+    pub fn run_selected(&mut self) {
+        let Some(i) = self.state.selected().filter(|&i| i < self.rows.len()) else {
+            return;
+        };
+
+        let (output, trace) = match run(&self.root, &mut self.rows[i]) {
+            Ok(runs) => (runs.timed.stdout, runs.checked.stderr),
+            Err(e) => (e.to_string(), String::new()),
+        };
+
+        self.outputs.insert(i, (output, trace));
+        self.scroll = 0;
+        self.message = HELP.to_owned();
+    }
+
+    pub fn draw(&mut self, frame: &mut Frame) {
+        let [table_area, panes_area, help_area] =
+            Layout::vertical([Constraint::Percentage(60), Constraint::Fill(1), Constraint::Length(1)])
+                .areas(frame.area());
+        let [output_area, trace_area] = Layout::horizontal([Constraint::Percentage(50); 2]).areas(panes_area);
+
+        let rows = self.rows.iter().map(|row| {
+            let style = match (row.check(), &row.status) {
+                (Check::Matches, _) => Style::new().fg(Color::Green),
+                (Check::Differs | Check::Failed, _) => Style::new().fg(Color::Red),
+                (_, Status::Missing) => Style::new().fg(Color::DarkGray),
+                _ => Style::new(),
+            };
+
+            TableRow::new(row.cells()).style(style)
+        });
+
+        let table = Table::new(rows, WIDTHS)
+            .header(TableRow::new(HEADER).style(Style::new().add_modifier(Modifier::BOLD)))
+            .block(Block::bordered().title(" days "))
+            .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+
+        frame.render_stateful_widget(table, table_area, &mut self.state);
+
+        let (output, trace) = match self.state.selected().and_then(|i| self.outputs.get(&i)) {
+            Some((output, trace)) => (output.as_str(), trace.as_str()),
+            None => ("not run yet, enter runs it", ""),
+        };
+
+        for (text, title, area) in [(output, " output ", output_area), (trace, " trace ", trace_area)] {
+            let pane = Paragraph::new(text)
+                .block(Block::bordered().title(title))
+                .wrap(Wrap { trim: false })
+                .scroll((self.scroll, 0));
+
+            frame.render_widget(pane, area);
+        }
+
+        frame.render_widget(Line::from(self.message.as_str()), help_area);
+    }
+
+    fn event_loop(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+
+            let selected = self.state.selected().unwrap_or(0);
+
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Down | KeyCode::Char('j') => self.select(selected + 1),
+                KeyCode::Up | KeyCode::Char('k') => self.select(selected.saturating_sub(1)),
+                KeyCode::PageDown => self.scroll = self.scroll.saturating_add(10),
+                KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+                KeyCode::Enter => {
+                    // Building and running blocks, say so before it starts
+                    if let Some(row) = self.rows.get(selected) {
+                        self.message = format!("running {}/{:02} {}...", row.year, row.day, row.part);
+                        terminal.draw(|frame| self.draw(frame))?;
+                    }
+
+                    self.run_selected();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Shows the dashboard until it is quit.
+    pub fn show(&mut self) -> io::Result<()> {
+        let mut terminal = ratatui::init();
+        let result = self.event_loop(&mut terminal);
+
+        ratatui::restore();
+        result
+    }
+}
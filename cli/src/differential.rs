@@ -0,0 +1,569 @@
+//! Differential checks of the parts whose solvers take shortcuts: each is run against a naive reference of the same
+//! puzzle on small generated inputs, and a disagreement is shrunk to a minimal input, see [`aoc::prop`].
+
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    fmt::Write,
+    ops::RangeInclusive,
+    path::Path,
+    sync::LazyLock,
+};
+
+use aoc::{
+    prop::{self, Failure},
+    rng::Rng,
+};
+use regex::Regex;
+
+use crate::{generator, runner};
+
+static NUMBER_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\d+").unwrap());
+
+pub const YEAR: u32 = 2025;
+
+pub struct Subject {
+    pub day: u32,
+    pub part: &'static str,
+    /// Sizes of the generated inputs, small enough for the reference.
+    sizes: RangeInclusive<usize>,
+    /// Turns a generated input into one the reference handles quickly, still valid.
+    reduce: fn(String, &mut Rng) -> String,
+    /// The answer, or `None` for an input the puzzle rules out, which shrinking must not lead to.
+    pub reference: fn(&str) -> Option<String>,
+    shrink: fn(&str) -> Vec<String>,
+}
+
+pub const SUBJECTS: [Subject; 5] = [
+    // Counting zero crossings in closed form
+    Subject {
+        day: 1,
+        part: "b",
+        sizes: 1..=20,
+        reduce: |text, _| text,
+        reference: clicks_at_zero,
+        shrink: |text| [lines_removed(text), numbers_shrunk(text)].concat(),
+    },
+    // Merging the ranges
+    Subject {
+        day: 5,
+        part: "a",
+        sizes: 1..=8,
+        reduce: |text, _| compress(&text, 1),
+        reference: fresh_ingredients,
+        shrink: |text| [lines_removed(text), vec![compress(text, 1)], numbers_shrunk(text)].concat(),
+    },
+    Subject {
+        day: 5,
+        part: "b",
+        sizes: 1..=8,
+        reduce: |text, _| compress(&text, 1),
+        reference: fresh_ids,
+        shrink: |text| [lines_removed(text), vec![compress(text, 1)], numbers_shrunk(text)].concat(),
+    },
+    // Testing rectangles against indexed edges
+    Subject {
+        day: 9,
+        part: "b",
+        sizes: 1..=6,
+        reduce: |text, _| compress(&text, 2),
+        reference: largest_inside,
+        shrink: |text| [steps_flattened(text), vec![compress(text, 2)], numbers_shrunk(text)].concat(),
+    },
+    // Simplifying the system, then searching with a memo
+    Subject {
+        day: 10,
+        part: "b",
+        sizes: 1..=3,
+        reduce: few_presses,
+        reference: fewest_presses,
+        shrink: |text| [lines_removed(text), buttons_removed(text), numbers_shrunk(text)].concat(),
+    },
+];
+
+impl Subject {
+    pub fn generate(&self, rng: &mut Rng) -> String {
+        let size = rng.between(*self.sizes.start() as u64, *self.sizes.end() as u64) as usize;
+        let text = generator::generate(self.day, size, rng).expect("sizes suit the generator");
+
+        (self.reduce)(text, rng)
+    }
+
+    /// Smaller inputs the puzzle allows.
+    pub fn shrink(&self, text: &str) -> Vec<String> {
+        let mut smaller = (self.shrink)(text);
+
+        smaller.retain(|smaller| smaller != text && (self.reference)(smaller).is_some());
+        smaller
+    }
+
+    /// Runs the part built at `executable` on `cases` generated inputs, comparing it with the reference.
+    pub fn check(&self, executable: &Path, rng: &mut Rng, cases: usize) -> Result<(), Failure<String>> {
+        prop::check(
+            rng,
+            cases,
+            |rng| self.generate(rng),
+            |text| self.shrink(text),
+            |text| {
+                let expected = (self.reference)(text).ok_or("the reference rejects the input")?;
+
+                match runner::run_built(executable, text) {
+                    Ok(actual) if actual == expected => Ok(()),
+                    Ok(actual) => Err(format!("expected {expected}, got {actual}")),
+                    Err(e) => Err(format!("expected {expected}, the part {e}")),
+                }
+            },
+        )
+    }
+}
+
+fn lines(lines: &[&str]) -> String {
+    lines.iter().map(|line| format!("{line}\n")).collect()
+}
+
+fn lines_removed(text: &str) -> Vec<String> {
+    let all: Vec<&str> = text.lines().collect();
+
+    prop::shrink_list(&all).iter().map(|kept| lines(kept)).collect()
+}
+
+/// The text with one of its numbers smaller.
+fn numbers_shrunk(text: &str) -> Vec<String> {
+    let mut smaller = Vec::new();
+
+    for found in NUMBER_REGEX.find_iter(text) {
+        let Ok(n) = found.as_str().parse::<u64>() else {
+            continue;
+        };
+
+        for m in prop::shrink_number(n) {
+            smaller.push(format!("{}{m}{}", &text[..found.start()], &text[found.end()..]));
+        }
+    }
+
+    smaller
+}
+
+/// Every number replaced by its rank among the text's numbers times `gap`, which keeps their order and makes them
+/// small.
+fn compress(text: &str, gap: u64) -> String {
+    let mut values: Vec<u64> = NUMBER_REGEX.find_iter(text).filter_map(|found| found.as_str().parse().ok()).collect();
+
+    values.sort_unstable();
+    values.dedup();
+
+    NUMBER_REGEX
+        .replace_all(text, |captures: &regex::Captures| {
+            let value: u64 = captures[0].parse().unwrap_or_default();
+
+            (values.partition_point(|&v| v < value) as u64 * gap).to_string()
+        })
+        .into_owned()
+}
+
+const DIAL_SIZE: u64 = 100;
+const DIAL_START: u64 = 50;
+
+/// Turns the dial one click at a time.
+fn clicks_at_zero(text: &str) -> Option<String> {
+    let mut position = DIAL_START;
+    let mut count = 0;
+
+    for line in text.lines() {
+        let steps: u64 = line.get(1..)?.parse().ok()?;
+        let click = match line.as_bytes()[0] {
+            b'L' => DIAL_SIZE - 1,
+            b'R' => 1,
+            _ => return None,
+        };
+
+        for _ in 0..steps {
+            position = (position + click) % DIAL_SIZE;
+            count += u64::from(position == 0);
+        }
+    }
+
+    Some(count.to_string())
+}
+
+/// Ranges and IDs.
+type Ingredients = (Vec<(u64, u64)>, Vec<u64>);
+
+/// Ranges, then a blank line, then IDs.
+fn ingredients(text: &str) -> Option<Ingredients> {
+    let all: Vec<&str> = text.lines().collect();
+    let blank = all.iter().position(|line| line.is_empty())?;
+    let mut ranges = Vec::new();
+
+    for line in &all[..blank] {
+        let (start, end) = line.split_once('-')?;
+        let (start, end) = (start.parse().ok()?, end.parse().ok()?);
+
+        if start > end {
+            return None;
+        }
+
+        ranges.push((start, end));
+    }
+
+    let ids = all[blank + 1..].iter().map(|line| line.parse().ok()).collect::<Option<_>>()?;
+
+    Some((ranges, ids))
+}
+
+/// Looks for each ID in every range.
+fn fresh_ingredients(text: &str) -> Option<String> {
+    let (ranges, ids) = ingredients(text)?;
+    let fresh = ids.iter().filter(|&&id| ranges.iter().any(|&(start, end)| (start..=end).contains(&id))).count();
+
+    Some(fresh.to_string())
+}
+
+/// Collects every ID of every range.
+fn fresh_ids(text: &str) -> Option<String> {
+    let (ranges, _) = ingredients(text)?;
+    let fresh: HashSet<u64> = ranges.iter().flat_map(|&(start, end)| start..=end).collect();
+
+    Some(fresh.len().to_string())
+}
+
+fn corners(text: &str) -> Option<Vec<(u64, u64)>> {
+    text.lines()
+        .map(|line| {
+            let (x, y) = line.split_once(',')?;
+
+            Some((x.parse().ok()?, y.parse().ok()?))
+        })
+        .collect()
+}
+
+/// Whether the corners make a loop of horizontal and vertical edges in turn, which neither crosses nor touches
+/// itself. As in puzzle inputs, distinct coordinates are never adjacent: the solver works on edges rather than tiles,
+/// and tiles would fill the gap between edges next to each other.
+fn is_loop(corners: &[(u64, u64)]) -> bool {
+    let n = corners.len();
+    let edge = |i: usize| (corners[i], corners[(i + 1) % n]);
+    let distinct: HashSet<_> = corners.iter().collect();
+
+    if n < 4 || distinct.len() != n {
+        return false;
+    }
+
+    for axis in [corners.iter().map(|c| c.0).collect::<Vec<_>>(), corners.iter().map(|c| c.1).collect()] {
+        let values: BTreeSet<u64> = axis.into_iter().collect();
+
+        if values.iter().zip(values.iter().skip(1)).any(|(a, b)| b - a < 2) {
+            return false;
+        }
+    }
+
+    for i in 0..n {
+        let ((a, b), (_, c)) = (edge(i), edge((i + 1) % n));
+
+        if (a.0 == b.0) == (a.1 == b.1) || (a.0 == b.0) == (b.0 == c.0) {
+            return false;
+        }
+    }
+
+    let span = |p: u64, q: u64| (p.min(q), p.max(q));
+
+    for i in 0..n {
+        for j in i + 2..n {
+            if i == 0 && j == n - 1 {
+                continue;
+            }
+
+            let ((a, b), (c, d)) = (edge(i), edge(j));
+            let (ax, ay, cx, cy) = (span(a.0, b.0), span(a.1, b.1), span(c.0, d.0), span(c.1, d.1));
+
+            if ax.0 <= cx.1 && cx.0 <= ax.1 && ay.0 <= cy.1 && cy.0 <= ay.1 {
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
+/// Draws the loop on a grid with a free border, floods the outside from that border, and tries every rectangle
+/// tile by tile.
+fn largest_inside(text: &str) -> Option<String> {
+    let corners = corners(text)?;
+
+    if !is_loop(&corners) {
+        return None;
+    }
+
+    let width = corners.iter().map(|c| c.0).max()? as usize + 3;
+    let height = corners.iter().map(|c| c.1).max()? as usize + 3;
+    let mut tiles = vec![vec![false; width]; height];
+
+    for (i, &(x0, y0)) in corners.iter().enumerate() {
+        let (x1, y1) = corners[(i + 1) % corners.len()];
+
+        for x in x0.min(x1)..=x0.max(x1) {
+            for y in y0.min(y1)..=y0.max(y1) {
+                tiles[y as usize + 1][x as usize + 1] = true;
+            }
+        }
+    }
+
+    let mut outside = vec![vec![false; width]; height];
+    let mut stack = vec![(0usize, 0usize)];
+
+    outside[0][0] = true;
+
+    while let Some((x, y)) = stack.pop() {
+        for (dx, dy) in [(1, 0), (0, 1), (-1, 0), (0, -1)] {
+            let (Some(x), Some(y)) = (x.checked_add_signed(dx), y.checked_add_signed(dy)) else {
+                continue;
+            };
+
+            if x < width && y < height && !tiles[y][x] && !outside[y][x] {
+                outside[y][x] = true;
+                stack.push((x, y));
+            }
+        }
+    }
+
+    let mut largest = 0;
+
+    for (i, &(ax, ay)) in corners.iter().enumerate() {
+        for &(bx, by) in &corners[i + 1..] {
+            let xs = ax.min(bx) as usize + 1..=ax.max(bx) as usize + 1;
+            let ys = ay.min(by) as usize + 1..=ay.max(by) as usize + 1;
+
+            if ys.clone().all(|y| xs.clone().all(|x| !outside[y][x])) {
+                largest = largest.max((ax.abs_diff(bx) + 1) * (ay.abs_diff(by) + 1));
+            }
+        }
+    }
+
+    Some(largest.to_string())
+}
+
+fn render_corners(corners: &[(u64, u64)]) -> String {
+    corners.iter().map(|(x, y)| format!("{x},{y}\n")).collect()
+}
+
+/// Loops with one step of the outline flattened: an edge moved onto the corner before it or after it, taking two
+/// corners away.
+fn steps_flattened(text: &str) -> Vec<String> {
+    let Some(corners) = corners(text) else {
+        return Vec::new();
+    };
+
+    let n = corners.len();
+    let mut smaller = Vec::new();
+
+    if n <= 4 {
+        return smaller;
+    }
+
+    for i in 0..n {
+        let (a, b, c, d) = (corners[i], corners[(i + 1) % n], corners[(i + 2) % n], corners[(i + 3) % n]);
+        let horizontal = b.1 == c.1;
+
+        let onto_a = if horizontal { (c.0, a.1) } else { (a.0, c.1) };
+        let onto_d = if horizontal { (b.0, d.1) } else { (d.0, b.1) };
+
+        let flattenings = [([i, (i + 1) % n], (i + 2) % n, onto_a), ([(i + 2) % n, (i + 3) % n], (i + 1) % n, onto_d)];
+
+        for (removed, moved, to) in flattenings {
+            let flattened: Vec<(u64, u64)> = (0..n)
+                .filter(|j| !removed.contains(j))
+                .map(|j| if j == moved { to } else { corners[j] })
+                .collect();
+
+            smaller.push(render_corners(&flattened));
+        }
+    }
+
+    smaller
+}
+
+struct Machine {
+    lights: usize,
+    buttons: Vec<Vec<usize>>,
+    joltages: Vec<u64>,
+}
+
+/// `[lights] (button) ... {joltages}`, every button lighting distinct lights of the machine.
+fn machine(line: &str) -> Option<Machine> {
+    let fields: Vec<&str> = line.split(' ').collect();
+    let (&indicators, rest) = fields.split_first()?;
+    let (&joltages, buttons) = rest.split_last()?;
+
+    let lights = indicators.strip_prefix('[')?.strip_suffix(']')?.len();
+    let joltages: Vec<u64> =
+        joltages.strip_prefix('{')?.strip_suffix('}')?.split(',').map(|j| j.parse().ok()).collect::<Option<_>>()?;
+
+    let mut wiring = Vec::new();
+
+    for button in buttons {
+        let button: Vec<usize> =
+            button.strip_prefix('(')?.strip_suffix(')')?.split(',').map(|l| l.parse().ok()).collect::<Option<_>>()?;
+
+        if button.iter().any(|&light| light >= lights) || button.iter().collect::<HashSet<_>>().len() != button.len() {
+            return None;
+        }
+
+        wiring.push(button);
+    }
+
+    (joltages.len() == lights && !wiring.is_empty()).then_some(Machine {
+        lights,
+        buttons: wiring,
+        joltages,
+    })
+}
+
+fn render_machine(machine: &Machine, indicators: &str) -> String {
+    let mut line = indicators.to_owned();
+
+    for button in &machine.buttons {
+        let button: Vec<String> = button.iter().map(usize::to_string).collect();
+
+        write!(line, " ({})", button.join(",")).unwrap();
+    }
+
+    let joltages: Vec<String> = machine.joltages.iter().map(u64::to_string).collect();
+
+    writeln!(line, " {{{}}}", joltages.join(",")).unwrap();
+    line
+}
+
+/// The same machines, with joltages from pressing each button at most twice.
+fn few_presses(text: String, rng: &mut Rng) -> String {
+    let mut reduced = String::new();
+
+    for line in text.lines() {
+        let Some(mut machine) = machine(line) else {
+            continue;
+        };
+
+        machine.joltages = vec![0; machine.lights];
+
+        for button in &machine.buttons {
+            let presses = rng.below(3);
+
+            for &light in button {
+                machine.joltages[light] += presses;
+            }
+        }
+
+        reduced.push_str(&render_machine(&machine, line.split(' ').next().unwrap_or_default()));
+    }
+
+    reduced
+}
+
+type Presses = HashMap<(usize, Vec<u64>), Option<u64>>;
+
+/// Fewest presses of buttons `from..` leaving every joltage at zero, trying every count for each button in turn.
+fn presses(machine: &Machine, from: usize, left: Vec<u64>, memo: &mut Presses) -> Option<u64> {
+    if from == machine.buttons.len() {
+        return left.iter().all(|&j| j == 0).then_some(0);
+    }
+
+    if let Some(&known) = memo.get(&(from, left.clone())) {
+        return known;
+    }
+
+    let button = &machine.buttons[from];
+    let most = button.iter().map(|&light| left[light]).min().unwrap_or(0);
+    let mut fewest = None;
+
+    for count in 0..=most {
+        let mut rest = left.clone();
+
+        for &light in button {
+            rest[light] -= count;
+        }
+
+        if let Some(more) = presses(machine, from + 1, rest, memo) {
+            fewest = Some(fewest.map_or(count + more, |fewest: u64| fewest.min(count + more)));
+        }
+    }
+
+    memo.insert((from, left), fewest);
+    fewest
+}
+
+fn fewest_presses(text: &str) -> Option<String> {
+    let mut total = 0;
+
+    for line in text.lines() {
+        let machine = machine(line)?;
+
+        total += presses(&machine, 0, machine.joltages.clone(), &mut HashMap::new())?;
+    }
+
+    Some(total.to_string())
+}
+
+/// The machines with one button taken off one of them.
+fn buttons_removed(text: &str) -> Vec<String> {
+    let all: Vec<&str> = text.lines().collect();
+    let mut smaller = Vec::new();
+
+    for (i, line) in all.iter().enumerate() {
+        let fields: Vec<&str> = line.split(' ').collect();
+
+        for button in 1..fields.len().saturating_sub(1) {
+            let mut fewer = fields.clone();
+
+            fewer.remove(button);
+
+            let mut machines = all.clone();
+            let fewer = fewer.join(" ");
+
+            machines[i] = &fewer;
+            smaller.push(lines(&machines));
+        }
+    }
+
+    smaller
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answer(day: u32, part: &str, input: &str) -> Option<String> {
+        let subject = SUBJECTS.iter().find(|subject| subject.day == day && subject.part == part).unwrap();
+
+        (subject.reference)(input)
+    }
+
+    #[test]
+    fn references_solve_inputs_small_enough_to_check_by_hand() {
+        assert_eq!(answer(1, "b", "R50\nL1\nR201\n").as_deref(), Some("4"), "the dial reference counts clicks");
+        assert_eq!(answer(5, "a", "1-3\n5-6\n2-4\n\n4\n7\n1\n").as_deref(), Some("2"), "fresh ingredients are counted");
+        assert_eq!(answer(5, "b", "1-3\n5-6\n2-4\n\n4\n7\n1\n").as_deref(), Some("6"), "fresh IDs are counted");
+        assert_eq!(answer(5, "a", "3-1\n\n1\n"), None, "reversed ranges are refused");
+        assert_eq!(answer(5, "a", "1-3\n\n1\n1\n").as_deref(), Some("2"), "repeated IDs count each time");
+        assert_eq!(answer(9, "b", "0,0\n4,0\n4,2\n0,2\n").as_deref(), Some("15"), "a rectangle fits itself");
+        assert_eq!(answer(9, "b", "0,0\n6,0\n6,2\n2,2\n2,6\n0,6\n").as_deref(), Some("21"), "rectangles stay inside");
+        assert_eq!(answer(9, "b", "0,0\n1,0\n1,2\n0,2\n"), None, "adjacent edges are refused");
+        assert_eq!(answer(10, "b", "[.#] (0) (0,1) {3,2}\n").as_deref(), Some("3"), "the fewest presses are found");
+        assert_eq!(answer(10, "b", "[.#] (0) {3,2}\n"), None, "machines without a solution are refused");
+    }
+
+    /// Only the first valid smaller input is looked for: running the reference on every one is what makes shrinking
+    /// slow.
+    #[test]
+    fn generated_inputs_are_valid_and_shrink() {
+        for subject in &SUBJECTS {
+            let mut rng = Rng::new(2);
+
+            for _ in 0..3 {
+                let input = subject.generate(&mut rng);
+                let (day, part) = (subject.day, subject.part);
+                let valid = |smaller: &String| *smaller != input && (subject.reference)(smaller).is_some();
+
+                assert!((subject.reference)(&input).is_some(), "{day}/{part}: generated inputs are valid");
+                assert!((subject.shrink)(&input).iter().any(valid), "{day}/{part}: generated inputs shrink");
+            }
+        }
+    }
+}
@@ -0,0 +1,216 @@
+//! Pulls the example inputs and their answers out of a puzzle page saved from the site, so they need not be copied
+//! by hand.
+
+use std::{
+    fs,
+    io::{self, BufRead, IsTerminal, Write},
+    path::Path,
+    sync::LazyLock,
+};
+
+use regex::Regex;
+
+use crate::{
+    fetch,
+    runner::{Example, PARTS},
+};
+
+static ARTICLE_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?s)<article[^>]*>(.*?)</article>").unwrap());
+static BLOCK_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?s)<pre><code>(.*?)</code></pre>").unwrap());
+static ANSWER_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)<code><em>(.*?)</em></code>|<em><code>(.*?)</code></em>").unwrap());
+static TAG_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"<[^>]*>").unwrap());
+
+/// Lines of a block shown when asking which one is the example.
+const PREVIEW: usize = 4;
+
+/// What the text of one part offers.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Part {
+    /// Every preformatted block, the example being one of them.
+    pub blocks: Vec<String>,
+    /// The last emphasised code of the text, which is where the puzzles state the example's answer.
+    pub answer: Option<String>,
+}
+
+fn text(html: &str) -> String {
+    TAG_REGEX
+        .replace_all(html, "")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// The parts found on a saved puzzle page, part two only once it was unlocked.
+pub fn parse(html: &str) -> Vec<Part> {
+    ARTICLE_REGEX
+        .captures_iter(html)
+        .take(PARTS.len())
+        .map(|article| {
+            let article = &article[1];
+
+            Part {
+                blocks: BLOCK_REGEX.captures_iter(article).map(|block| text(&block[1])).collect(),
+                answer: ANSWER_REGEX
+                    .captures_iter(article)
+                    .last()
+                    .and_then(|answer| answer.get(1).or(answer.get(2)))
+                    .map(|answer| text(answer.as_str()).trim().to_owned()),
+            }
+        })
+        .collect()
+}
+
+/// Which block a part's example is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Choice {
+    Block(usize),
+    /// Part two reusing the example of part one.
+    Same,
+}
+
+/// Asks on the terminal which of several blocks is the example, unless `picked` already says. Part two may also
+/// reuse part one's example, offered as choice 0.
+fn choose(part: &str, blocks: &[String], same: bool, picked: Option<usize>) -> io::Result<Choice> {
+    let choice = |n: usize| match n {
+        0 if same => Some(Choice::Same),
+        n if (1..=blocks.len()).contains(&n) => Some(Choice::Block(n - 1)),
+        _ => None,
+    };
+
+    if let Some(n) = picked {
+        return choice(n).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, format!("part {part} has no block {n}"))
+        });
+    }
+
+    if blocks.len() == 1 && !same {
+        return Ok(Choice::Block(0));
+    }
+
+    if blocks.is_empty() {
+        return Ok(Choice::Same);
+    }
+
+    let flag = if part == PARTS[0] { "--pick" } else { "--pick-b" };
+
+    if !io::stdin().is_terminal() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("part {part} has several candidate blocks, choose one with {flag} N"),
+        ));
+    }
+
+    eprintln!("part {part} has several candidate blocks:");
+
+    if same {
+        eprintln!("  0: the example of part {}", PARTS[0]);
+    }
+
+    for (i, block) in blocks.iter().enumerate() {
+        let lines: Vec<&str> = block.lines().collect();
+
+        eprintln!("  {}: {} lines", i + 1, lines.len());
+
+        for line in lines.iter().take(PREVIEW) {
+            eprintln!("       {line}");
+        }
+
+        if lines.len() > PREVIEW {
+            eprintln!("       ...");
+        }
+    }
+
+    let mut stdin = io::stdin().lock();
+
+    loop {
+        eprint!("example for part {part}? ");
+        io::stderr().flush()?;
+
+        let mut line = String::new();
+
+        if stdin.read_line(&mut line)? == 0 {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "no block chosen"));
+        }
+
+        if let Some(choice) = line.trim().parse().ok().and_then(choice) {
+            return Ok(choice);
+        }
+    }
+}
+
+/// The example fixtures taken from a page.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fixtures {
+    /// Parts the page had text for.
+    pub parts: usize,
+    /// Each part's example, part two's only when it differs from part one's.
+    pub inputs: [Option<String>; 2],
+    pub answers: [Option<String>; 2],
+}
+
+/// Picks the examples of both parts out of a page, asking when the choice is not obvious and `picks` does not make
+/// it. Part two without a block of its own reuses the example of part one.
+pub fn extract(html: &str, picks: [Option<usize>; 2]) -> io::Result<Fixtures> {
+    let parts = parse(html);
+    let Some(first) = parts.first() else {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "no puzzle text on the page"));
+    };
+
+    if first.blocks.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "part a has no example block"));
+    }
+
+    let mut fixtures = Fixtures {
+        parts: parts.len(),
+        inputs: [None, None],
+        answers: [first.answer.clone(), None],
+    };
+
+    let Choice::Block(i) = choose(PARTS[0], &first.blocks, false, picks[0])? else {
+        unreachable!("part a always has a block of its own");
+    };
+
+    fixtures.inputs[0] = Some(first.blocks[i].clone());
+
+    if let Some(second) = parts.get(1) {
+        fixtures.answers[1] = second.answer.clone();
+
+        if let Choice::Block(i) = choose(PARTS[1], &second.blocks, true, picks[1])?
+            && fixtures.inputs[0].as_ref() != Some(&second.blocks[i])
+        {
+            fixtures.inputs[1] = Some(second.blocks[i].clone());
+        }
+    }
+
+    Ok(fixtures)
+}
+
+/// Writes the examples next to the day's parts. Answers the page does not give are kept from earlier fixtures.
+pub fn write(day_dir: &Path, fixtures: &Fixtures) -> io::Result<()> {
+    if let Some(input) = &fixtures.inputs[0] {
+        fetch::write_atomically(&Example::input_path(day_dir), input)?;
+    }
+
+    let second = Example::second_input_path(day_dir);
+
+    match &fixtures.inputs[1] {
+        Some(input) => fetch::write_atomically(&second, input)?,
+        None if fixtures.parts == PARTS.len() && second.exists() => fs::remove_file(second)?,
+        None => {}
+    }
+
+    let mut answers = fixtures.answers.clone();
+
+    if let Some(example) = Example::load(day_dir)? {
+        for (answer, known) in answers.iter_mut().zip(example.answers) {
+            if answer.is_none() {
+                *answer = known;
+            }
+        }
+    }
+
+    Example::write_answers(day_dir, &answers)
+}
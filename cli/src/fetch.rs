@@ -0,0 +1,185 @@
+use std::{
+    fs,
+    io,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use ureq::{
+    Agent,
+    Body,
+    http::Response,
+};
+
+use crate::config::Config;
+
+/// Shortest wait between two requests to the site, whatever they ask for.
+pub const INTERVAL: Duration = Duration::from_secs(5);
+
+/// Days with a puzzle in `year`: 25 a year until 2024, 12 from 2025 on.
+pub const fn days(year: u32) -> RangeInclusive<u32> {
+    if year >= 2025 { 1..=12 } else { 1..=25 }
+}
+
+/// Downloads from the site, keeping every input it fetched under its cache directory so no input is requested twice.
+pub struct Client {
+    agent: Agent,
+    config: Config,
+    cache: PathBuf,
+    interval: Duration,
+}
+
+/// A day's input and whether it came from the cache.
+pub struct Fetched {
+    pub text: String,
+    pub cached: bool,
+}
+
+impl Client {
+    pub fn new(config: Config, cache: PathBuf) -> Self {
+        let agent = Agent::config_builder()
+            .user_agent(config.user_agent())
+            .http_status_as_error(false)
+            .timeout_global(Some(Duration::from_secs(30)))
+            .build()
+            .into();
+
+        Client {
+            agent,
+            config,
+            cache,
+            interval: INTERVAL,
+        }
+    }
+
+    pub fn with_interval(self, interval: Duration) -> Self {
+        Client { interval, ..self }
+    }
+
+    pub fn cache(&self) -> &Path {
+        &self.cache
+    }
+
+    fn input_path(&self, year: u32, day: u32) -> PathBuf {
+        self.cache.join("inputs").join(year.to_string()).join(format!("{day:02}.txt"))
+    }
+
+    /// The input of a day, downloaded unless it is cached already or `refresh` asks to download it again.
+    pub fn input(&self, year: u32, day: u32, refresh: bool) -> io::Result<Fetched> {
+        check_day(year, day)?;
+
+        let path = self.input_path(year, day);
+
+        if !refresh {
+            match fs::read_to_string(&path) {
+                Ok(text) => return Ok(Fetched { text, cached: true }),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        let text = self.get(&format!("/{year}/day/{day}/input"))?;
+
+        if text.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, format!("{year} day {day}: empty input")));
+        }
+
+        write_atomically(&path, &text)?;
+
+        Ok(Fetched { text, cached: false })
+    }
+
+    /// Body of a successful GET of `path` on the site, sent with the session cookie.
+    pub fn get(&self, path: &str) -> io::Result<String> {
+        let session = self.config.session()?;
+
+        self.throttle()?;
+
+        let url = format!("{}{path}", self.config.base_url);
+        let response = self.agent.get(&url).header("Cookie", format!("session={session}")).call();
+
+        body(&url, response)
+    }
+
+    /// Body of a successful form POST to `path` on the site, sent with the session cookie.
+    pub fn post(&self, path: &str, form: &[(&str, &str)]) -> io::Result<String> {
+        let session = self.config.session()?;
+
+        self.throttle()?;
+
+        let url = format!("{}{path}", self.config.base_url);
+        let response = self
+            .agent
+            .post(&url)
+            .header("Cookie", format!("session={session}"))
+            .send_form(form.iter().copied());
+
+        body(&url, response)
+    }
+
+    /// Waits until the interval since the last request, from this or any earlier run, has passed and records the
+    /// request about to be made.
+    fn throttle(&self) -> io::Result<()> {
+        let stamp = self.cache.join("last-request");
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+
+        let last = fs::read_to_string(&stamp)
+            .ok()
+            .and_then(|text| text.trim().parse::<u64>().ok())
+            .map(Duration::from_millis);
+
+        if let Some(wait) = last.and_then(|last| (last + self.interval).checked_sub(now))
+            && !wait.is_zero()
+        {
+            eprintln!("waiting {wait:.1?} before the next request");
+            thread::sleep(wait);
+        }
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+
+        fs::create_dir_all(&self.cache)?;
+        fs::write(stamp, now.as_millis().to_string())
+    }
+}
+
+fn body(url: &str, response: Result<Response<Body>, ureq::Error>) -> io::Result<String> {
+    let mut response = response.map_err(|e| io::Error::other(format!("{url}: {e}")))?;
+    let status = response.status().as_u16();
+    let body = response
+        .body_mut()
+        .read_to_string()
+        .map_err(|e| io::Error::other(format!("{url}: {e}")))?;
+
+    match status {
+        200 => Ok(body),
+        400 | 401 | 403 => Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!("{url}: session rejected (HTTP {status}), it may have expired"),
+        )),
+        404 => Err(io::Error::new(io::ErrorKind::NotFound, format!("{url}: not available yet (HTTP 404)"))),
+        _ => Err(io::Error::other(format!("{url}: HTTP {status}: {}", body.lines().next().unwrap_or("")))),
+    }
+}
+
+pub fn check_day(year: u32, day: u32) -> io::Result<()> {
+    if year < 2015 || !days(year).contains(&day) {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("no puzzle for {year} day {day}")));
+    }
+
+    Ok(())
+}
+
+/// Writes through a temporary file, so an interrupted download never leaves a truncated input behind. The temporary
+/// `.partial` file is ignored by git and refused by the staged check, since it may hold a plaintext input.
+pub fn write_atomically(path: &Path, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let partial = path.with_extension("partial");
+
+    fs::write(&partial, contents)?;
+    fs::rename(partial, path)
+}
@@ -0,0 +1,497 @@
+//! Synthetic inputs for the days of 2025, from a seed: valid for the day's parser, of any size, for stress
+//! benchmarks and for randomised checks of the solvers. Sizes count a day's records, see [`size_of`].
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet},
+    fmt::Write,
+    io,
+    ops::RangeInclusive,
+};
+
+use aoc::rng::Rng;
+
+use crate::fetch;
+
+pub const DAYS: RangeInclusive<u32> = fetch::days(2025);
+
+/// What a size counts for each day, its smallest valid size and the size of a puzzle input.
+const SIZES: [(&str, usize, usize); 12] = [
+    ("rotations", 1, 4000),
+    ("ranges", 1, 40),
+    ("banks", 1, 200),
+    ("rows and columns", 1, 137),
+    ("fresh ranges", 1, 180),
+    ("problems", 1, 1000),
+    ("levels of splitters", 1, 40),
+    ("junction boxes", MIN_BOXES, 1000),
+    ("columns of the polygon", 1, 125),
+    ("machines", 1, 180),
+    ("servers", 4, 600),
+    ("regions", 1, 1000),
+];
+
+fn sizes(day: u32) -> io::Result<(&'static str, usize, usize)> {
+    if !DAYS.contains(&day) {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("no generator for day {day}")));
+    }
+
+    Ok(SIZES[day as usize - 1])
+}
+
+/// What the size of a day's input counts.
+pub fn size_of(day: u32) -> io::Result<&'static str> {
+    sizes(day).map(|(what, _, _)| what)
+}
+
+/// Size of a puzzle input for the day.
+pub fn default_size(day: u32) -> io::Result<usize> {
+    sizes(day).map(|(_, _, size)| size)
+}
+
+/// An input for `day` of `size` records.
+pub fn generate(day: u32, size: usize, rng: &mut Rng) -> io::Result<String> {
+    let (what, minimum, _) = sizes(day)?;
+
+    if size < minimum {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("day {day} needs at least {minimum} {what}"),
+        ));
+    }
+
+    Ok(match day {
+        1 => rotations(size, rng),
+        2 => id_ranges(size, rng),
+        3 => banks(size, rng),
+        4 => rolls(size, rng),
+        5 => ingredients(size, rng),
+        6 => worksheet(size, rng),
+        7 => manifold(size, rng),
+        8 => junction_boxes(size, rng),
+        9 => polygon(size, rng),
+        10 => machines(size, rng),
+        11 => servers(size, rng),
+        _ => presents(size, rng),
+    })
+}
+
+fn rotations(size: usize, rng: &mut Rng) -> String {
+    let mut text = String::new();
+
+    for _ in 0..size {
+        let direction = if rng.chance(0.5) { 'L' } else { 'R' };
+
+        writeln!(text, "{direction}{}", rng.between(1, 999)).unwrap();
+    }
+
+    text
+}
+
+/// The solver looks at every ID of a range, so ranges stay this narrow.
+const RANGE_WIDTH: u64 = 100_000;
+const LARGEST_ID: u64 = 9_999_999_999;
+
+fn id_ranges(size: usize, rng: &mut Rng) -> String {
+    let mut ranges = BTreeMap::new();
+
+    // IDs of every length are as likely, and ranges do not overlap
+    while ranges.len() < size {
+        let digits = rng.between(1, 10) as u32;
+        let start = rng.between(10u64.pow(digits - 1), 10u64.pow(digits) - 1);
+        let end = (start + rng.below(RANGE_WIDTH)).min(LARGEST_ID);
+
+        if ranges.range(..=end).next_back().is_none_or(|(_, &previous_end)| previous_end < start) {
+            ranges.insert(start, end);
+        }
+    }
+
+    let mut ranges: Vec<String> = ranges.into_iter().map(|(start, end)| format!("{start}-{end}")).collect();
+
+    rng.shuffle(&mut ranges);
+    ranges.join(",") + "\n"
+}
+
+const BANK_LEN: usize = 100;
+
+fn digits(len: usize, rng: &mut Rng) -> String {
+    (0..len).map(|_| char::from(b'0' + rng.between(1, 9) as u8)).collect()
+}
+
+fn banks(size: usize, rng: &mut Rng) -> String {
+    (0..size).map(|_| digits(BANK_LEN, rng) + "\n").collect()
+}
+
+fn rolls(size: usize, rng: &mut Rng) -> String {
+    let mut text = String::new();
+
+    for _ in 0..size {
+        text.extend((0..size).map(|_| if rng.chance(0.6) { '@' } else { '.' }));
+        text.push('\n');
+    }
+
+    text
+}
+
+const LARGEST_INGREDIENT: u64 = 500_000_000_000_000;
+const FRESH_WIDTH: u64 = 20_000_000_000_000;
+const INGREDIENTS_PER_RANGE: usize = 5;
+
+fn ingredients(size: usize, rng: &mut Rng) -> String {
+    let ranges: Vec<(u64, u64)> = (0..size)
+        .map(|_| {
+            let start = rng.between(1, LARGEST_INGREDIENT);
+
+            (start, start + rng.below(FRESH_WIDTH))
+        })
+        .collect();
+
+    let mut text = String::new();
+
+    for (start, end) in &ranges {
+        writeln!(text, "{start}-{end}").unwrap();
+    }
+
+    text.push('\n');
+
+    // Half of them in some range, so there are fresh ones however sparse the ranges
+    for _ in 0..size * INGREDIENTS_PER_RANGE {
+        let id = if rng.chance(0.5) {
+            let &(start, end) = rng.pick(&ranges);
+
+            rng.between(start, end)
+        } else {
+            rng.between(1, LARGEST_INGREDIENT + FRESH_WIDTH)
+        };
+
+        writeln!(text, "{id}").unwrap();
+    }
+
+    text
+}
+
+const OPERANDS: usize = 4;
+const OPERAND_DIGITS: usize = 4;
+
+fn worksheet(size: usize, rng: &mut Rng) -> String {
+    let mut rows = vec![String::new(); OPERANDS + 1];
+
+    for problem in 0..size {
+        if problem > 0 {
+            rows.iter_mut().for_each(|row| row.push(' '));
+        }
+
+        // The longest operand spans the problem's columns, and the others line up on its left or its right
+        let width = rng.between(1, OPERAND_DIGITS as u64) as usize;
+        let longest = rng.index(OPERANDS);
+        let left = rng.chance(0.5);
+
+        for (i, row) in rows[..OPERANDS].iter_mut().enumerate() {
+            let len = if i == longest { width } else { rng.between(1, width as u64) as usize };
+            let operand = digits(len, rng);
+
+            if left {
+                write!(row, "{operand:<width$}").unwrap();
+            } else {
+                write!(row, "{operand:>width$}").unwrap();
+            }
+        }
+
+        let operator = if rng.chance(0.5) { '+' } else { '*' };
+
+        write!(rows[OPERANDS], "{operator:<width$}").unwrap();
+    }
+
+    rows.iter().map(|row| format!("{row}\n")).collect()
+}
+
+const SPLITTER_CHANCE: f64 = 0.7;
+
+fn manifold(size: usize, rng: &mut Rng) -> String {
+    let width = 2 * size + 1;
+    let mut rows = vec![vec![b'.'; width]; 2 * size + 2];
+
+    rows[0][size] = b'S';
+
+    // Beams only ever reach every other column of the rows below the start, widening by one each level
+    for level in 1..=size {
+        for i in 0..level {
+            if rng.chance(SPLITTER_CHANCE) {
+                rows[2 * level][size + 1 - level + 2 * i] = b'^';
+            }
+        }
+    }
+
+    rows.into_iter().map(|row| String::from_utf8(row).unwrap() + "\n").collect()
+}
+
+/// Day 8 joins the 1000 closest pairs, which takes this many boxes.
+const MIN_BOXES: usize = 46;
+const SPACE: u64 = 100_000;
+
+fn junction_boxes(size: usize, rng: &mut Rng) -> String {
+    let mut seen = HashSet::new();
+    let mut text = String::new();
+
+    while seen.len() < size {
+        let position = (rng.below(SPACE), rng.below(SPACE), rng.below(SPACE));
+
+        if seen.insert(position) {
+            writeln!(text, "{},{},{}", position.0, position.1, position.2).unwrap();
+        }
+    }
+
+    text
+}
+
+/// A loop of red tiles around columns of differing heights, each overlapping the next: a rectilinear polygon
+/// without holes or touching edges. It is drawn on every other tile, since in puzzle inputs edges are never next to
+/// each other.
+fn polygon(size: usize, rng: &mut Rng) -> String {
+    let space = SPACE / 2;
+    let step = (space / size as u64).max(2);
+    let mut xs = vec![rng.below(step)];
+
+    for _ in 0..size {
+        xs.push(xs[xs.len() - 1] + rng.between(1, step));
+    }
+
+    let mut columns: Vec<(u64, u64)> = Vec::with_capacity(size);
+
+    while columns.len() < size {
+        let top = rng.between(1, space);
+        let bottom = rng.below(top);
+
+        let fits = columns.last().is_none_or(|&(previous_top, previous_bottom)| {
+            top != previous_top && bottom != previous_bottom && bottom.max(previous_bottom) < top.min(previous_top)
+        });
+
+        if fits {
+            columns.push((top, bottom));
+        }
+    }
+
+    let mut corners = Vec::with_capacity(4 * size);
+
+    for (i, &(top, _)) in columns.iter().enumerate() {
+        corners.extend([(xs[i], top), (xs[i + 1], top)]);
+    }
+
+    for (i, &(_, bottom)) in columns.iter().enumerate().rev() {
+        corners.extend([(xs[i + 1], bottom), (xs[i], bottom)]);
+    }
+
+    let transpose = rng.chance(0.5);
+    let mut text = String::new();
+
+    for (x, y) in corners {
+        let (x, y) = if transpose { (y, x) } else { (x, y) };
+
+        writeln!(text, "{},{}", 2 * x, 2 * y).unwrap();
+    }
+
+    text
+}
+
+const MAX_PRESSES: u64 = 20;
+
+/// Machines whose buttons are pressed a random number of times for the joltages, and a random subset of them once
+/// for the lights, so both parts have a solution.
+fn machines(size: usize, rng: &mut Rng) -> String {
+    let mut text = String::new();
+
+    for _ in 0..size {
+        let lights = rng.between(3, 10) as usize;
+        let buttons = rng.between(lights as u64 - 1, lights as u64 + 3) as usize;
+        let mut indicators = vec![false; lights];
+        let mut joltages = vec![0; lights];
+        let mut wiring = Vec::with_capacity(buttons);
+
+        for _ in 0..buttons {
+            let mut button: Vec<usize> = (0..lights).collect();
+
+            rng.shuffle(&mut button);
+            button.truncate(rng.between(1, lights as u64 / 2 + 1) as usize);
+            button.sort_unstable();
+
+            let presses = rng.below(MAX_PRESSES + 1);
+            let toggled = rng.chance(0.5);
+
+            for &light in &button {
+                joltages[light] += presses;
+                indicators[light] ^= toggled;
+            }
+
+            wiring.push(button);
+        }
+
+        text.push('[');
+        text.extend(indicators.iter().map(|&on| if on { '#' } else { '.' }));
+        text.push(']');
+
+        for button in wiring {
+            let button: Vec<String> = button.iter().map(usize::to_string).collect();
+
+            write!(text, " ({})", button.join(",")).unwrap();
+        }
+
+        let joltages: Vec<String> = joltages.iter().map(u64::to_string).collect();
+
+        writeln!(text, " {{{}}}", joltages.join(",")).unwrap();
+    }
+
+    text
+}
+
+/// Paths through a layer multiply, so servers are spread over at most this many, keeping the path counts in range.
+const MAX_LAYERS: usize = 16;
+const NAMED: [&str; 5] = ["svr", "you", "fft", "dac", "out"];
+
+/// A layered network from `svr` to `out`, each server connecting to some of the next layer's and reached from the
+/// previous one, with a path from `fft` to `dac` and `you` close to `out`.
+fn servers(size: usize, rng: &mut Rng) -> String {
+    let layer_count = (size / 2).clamp(3, MAX_LAYERS);
+    let mut widths = vec![1; layer_count];
+
+    for _ in layer_count..size {
+        widths[rng.between(1, layer_count as u64 - 1) as usize] += 1;
+    }
+
+    let mut layers = Vec::with_capacity(layer_count);
+    let mut next = 0;
+
+    for width in widths {
+        layers.push((next..next + width).collect::<Vec<usize>>());
+        next += width;
+    }
+
+    let fft_layer = rng.between(1, layer_count as u64 - 2) as usize;
+    let dac_layer = rng.between(fft_layer as u64 + 1, layer_count as u64 - 1) as usize;
+    let fft = *rng.pick(&layers[fft_layer]);
+    let dac = *rng.pick(&layers[dac_layer]);
+
+    // The last three layers have three servers at least, so one of them is neither
+    let near_out: Vec<usize> = layers[(layer_count - 3).max(1)..]
+        .iter()
+        .flatten()
+        .copied()
+        .filter(|&server| server != fft && server != dac)
+        .collect();
+
+    let you = *rng.pick(&near_out);
+
+    let mut names = Vec::with_capacity(size + 1);
+    let mut used: HashSet<String> = NAMED.iter().map(|&name| name.to_owned()).collect();
+
+    while names.len() < size {
+        let name: String = (0..3).map(|_| char::from(b'a' + rng.below(26) as u8)).collect();
+
+        if used.insert(name.clone()) {
+            names.push(name);
+        }
+    }
+
+    for (server, name) in [(0, "svr"), (fft, "fft"), (dac, "dac"), (you, "you")] {
+        names[server] = name.to_owned();
+    }
+
+    let out = names.len();
+
+    names.push("out".to_owned());
+
+    let mut connections = vec![BTreeSet::new(); size];
+
+    for pair in layers.windows(2) {
+        let (layer, next) = (&pair[0], &pair[1]);
+
+        for &server in layer {
+            for _ in 0..rng.between(1, 3) {
+                connections[server].insert(*rng.pick(next));
+            }
+        }
+
+        for &server in next {
+            connections[*rng.pick(layer)].insert(server);
+        }
+    }
+
+    let mut on_path = fft;
+
+    for layer in &layers[fft_layer + 1..=dac_layer] {
+        let step = if layer.contains(&dac) { dac } else { *rng.pick(layer) };
+
+        connections[on_path].insert(step);
+        on_path = step;
+    }
+
+    for &server in &layers[layer_count - 1] {
+        connections[server].insert(out);
+    }
+
+    let mut lines: Vec<String> = connections
+        .iter()
+        .enumerate()
+        .map(|(server, connected)| {
+            let connected: Vec<&str> = connected.iter().map(|&c| names[c].as_str()).collect();
+
+            format!("{}: {}\n", names[server], connected.join(" "))
+        })
+        .collect();
+
+    rng.shuffle(&mut lines);
+    lines.concat()
+}
+
+const SHAPES: usize = 6;
+const SHAPE_SIDE: usize = 3;
+
+/// Regions either roomy enough to give every present its own 3x3 square, or smaller than the presents' cells, so
+/// the solver decides each of them quickly.
+fn presents(size: usize, rng: &mut Rng) -> String {
+    let mut text = String::new();
+    let mut cells = Vec::with_capacity(SHAPES);
+
+    for shape in 0..SHAPES {
+        let mut filled = vec![false; SHAPE_SIDE * SHAPE_SIDE];
+        let count = rng.between(5, 7) as usize;
+
+        filled[..count].fill(true);
+        rng.shuffle(&mut filled);
+        cells.push(count);
+
+        writeln!(text, "{shape}:").unwrap();
+
+        for row in filled.chunks(SHAPE_SIDE) {
+            text.extend(row.iter().map(|&filled| if filled { '#' } else { '.' }));
+            text.push('\n');
+        }
+
+        text.push('\n');
+    }
+
+    for _ in 0..size {
+        let width = rng.between(SHAPE_SIDE as u64, 50) as usize;
+        let height = rng.between(SHAPE_SIDE as u64, 50) as usize;
+        let mut quantities = [0; SHAPES];
+
+        if rng.chance(0.5) {
+            for _ in 0..rng.between(1, ((width / SHAPE_SIDE) * (height / SHAPE_SIDE)) as u64) {
+                quantities[rng.index(SHAPES)] += 1;
+            }
+        } else {
+            let mut filled = 0;
+
+            while filled <= width * height {
+                let shape = rng.index(SHAPES);
+
+                quantities[shape] += 1;
+                filled += cells[shape];
+            }
+        }
+
+        let quantities: Vec<String> = quantities.iter().map(usize::to_string).collect();
+
+        writeln!(text, "{width}x{height}: {}", quantities.join(" ")).unwrap();
+    }
+
+    text
+}
@@ -0,0 +1,13 @@
+pub mod config;
+pub mod dashboard;
+pub mod differential;
+pub mod examples;
+pub mod fetch;
+pub mod generator;
+pub mod paths;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod seal;
+pub mod submit;
+pub mod watch;
@@ -0,0 +1,388 @@
+use std::{
+    env,
+    fs,
+    io,
+    path::{Path, PathBuf},
+};
+
+use aoc::{
+    rng::Rng,
+    vault::Key,
+};
+use aoc_cli::{
+    config::Config,
+    dashboard::Dashboard,
+    differential::{self, SUBJECTS},
+    examples,
+    fetch::{self, Client},
+    generator,
+    paths,
+    report::{self, Options},
+    runner::{self, PARTS, Run},
+    scaffold::{self, Template},
+    seal,
+    submit::{self, History, Verdict},
+    watch,
+};
+
+const USAGE: &str = "usage: aoc fetch <year> <day> [--refresh]
+       aoc submit <year> <day> <part> [answer]
+       aoc new <year> <day> [--template lines|grid|sections]
+       aoc run [year [day]] [--example]
+       aoc examples <year> <day> <saved page> [--pick N] [--pick-b N]
+       aoc watch <year> <day>
+       aoc gen <day> [--size N] [--seed N]
+       aoc differential [day [part]] [--cases N] [--seed N]
+       aoc dashboard
+       aoc report [year] [--redact] [--source-url URL] [--out DIR]
+       aoc key
+       aoc seal [year [day]]
+       aoc rekey <new key file>
+       aoc check-staged [--install]";
+
+fn usage() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, USAGE)
+}
+
+fn number(arg: Option<&String>) -> io::Result<u32> {
+    arg.and_then(|arg| arg.parse().ok()).ok_or_else(usage)
+}
+
+/// Arguments other than flags. Flags taking a value must come after the positional arguments.
+fn positional(args: &[String]) -> Vec<&String> {
+    args.iter().take_while(|arg| !arg.starts_with("--")).collect()
+}
+
+/// Downloads a day's input, or takes it from the cache, and puts it where the day's crates read it.
+fn fetch(args: &[String]) -> io::Result<()> {
+    let positional = positional(args);
+    let refresh = args.iter().any(|arg| arg == "--refresh");
+    let (year, day) = (number(positional.first().copied())?, number(positional.get(1).copied())?);
+
+    let root = paths::root()?;
+    let client = Client::new(Config::load()?, paths::cache(&root));
+    let fetched = client.input(year, day, refresh)?;
+    let path = paths::day(&root, year, day).join("input");
+
+    if fs::read_to_string(&path).ok().as_deref() != Some(fetched.text.as_str()) {
+        fetch::write_atomically(&path, &fetched.text)?;
+    }
+
+    eprintln!(
+        "{year} day {day}: {} input, {} lines",
+        if fetched.cached { "cached" } else { "downloaded" },
+        fetched.text.lines().count()
+    );
+    println!("{}", path.display());
+
+    Ok(())
+}
+
+/// Submits an answer, by default the one the part's solver prints, unless the answers submitted before rule it out.
+fn submit(args: &[String]) -> io::Result<()> {
+    let (year, day, part) = (number(args.first())?, number(args.get(1))?, number(args.get(2))?);
+
+    let root = paths::root()?;
+    let answer = match (args.get(3), PARTS.get(part.wrapping_sub(1) as usize)) {
+        (Some(answer), _) => answer.clone(),
+        (None, Some(dir)) => runner::run(&paths::day(&root, year, day).join(dir))?,
+        (None, None) => return Err(usage()),
+    };
+
+    let client = Client::new(Config::load()?, paths::cache(&root));
+    let mut history = History::load(History::path(client.cache(), year, day, part))?;
+    let response = submit::submit(&client, &mut history, year, day, part, &answer)?;
+
+    match response.verdict {
+        Verdict::Judged(judgement) => println!("{answer}: {judgement}"),
+        Verdict::TooSoon => println!("{answer}: not judged, submitted too soon"),
+        Verdict::Solved => println!("{answer}: not judged, the part is solved already"),
+        Verdict::Unknown(text) => println!("{answer}: {text}"),
+    }
+
+    if let Some(wait) = response.wait {
+        println!("next answer in {}s", wait.as_secs());
+    }
+
+    Ok(())
+}
+
+/// Creates the crates of a new day and registers it.
+fn new(args: &[String]) -> io::Result<()> {
+    let (year, day) = (number(args.first())?, number(args.get(1))?);
+    let template = aoc::args::value::<Template>("--template")?.unwrap_or(Template::Lines);
+
+    let root = paths::root()?;
+
+    for path in scaffold::new_day(&root, year, day, template)? {
+        println!("created {}", path.strip_prefix(&root).unwrap_or(&path).display());
+    }
+
+    Ok(())
+}
+
+/// Runs every registered day, or the days of a year, or a single day, on the inputs or on the examples.
+fn run(args: &[String]) -> io::Result<()> {
+    let positional = positional(args);
+    let example = args.iter().any(|arg| arg == "--example");
+    let year = positional.first().map(|&arg| number(Some(arg))).transpose()?;
+    let day = positional.get(1).map(|&arg| number(Some(arg))).transpose()?;
+
+    let root = paths::root()?;
+    let mut failed = 0;
+
+    for (y, d) in runner::registered(&root)? {
+        if year.is_some_and(|year| year != y) || day.is_some_and(|day| day != d) {
+            continue;
+        }
+
+        for (part, run) in runner::run_day(&root, y, d, example)? {
+            match run {
+                Run::Answer(answer) => println!("{y}/{d:02} {part}: {answer}"),
+                Run::Passed(answer) => println!("{y}/{d:02} {part}: {answer} ok"),
+                Run::Failed { expected, actual } => {
+                    println!("{y}/{d:02} {part}: {actual} FAILED, expected {expected}");
+                    failed += 1;
+                }
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(io::Error::other(format!("{failed} parts failed")));
+    }
+
+    Ok(())
+}
+
+/// Writes the examples of a saved puzzle page, and the answers it gives for them, as the day's example fixtures.
+fn examples(args: &[String]) -> io::Result<()> {
+    let positional = positional(args);
+    let (year, day) = (number(positional.first().copied())?, number(positional.get(1).copied())?);
+    let page = positional.get(2).ok_or_else(usage)?;
+    let picks = [aoc::args::value("--pick")?, aoc::args::value("--pick-b")?];
+
+    let root = paths::root()?;
+    let day_dir = paths::day(&root, year, day);
+    let fixtures = examples::extract(&fs::read_to_string(page)?, picks)?;
+
+    examples::write(&day_dir, &fixtures)?;
+
+    for (i, part) in PARTS.iter().enumerate().take(fixtures.parts) {
+        let input = match &fixtures.inputs[i] {
+            Some(input) => format!("{} lines", input.lines().count()),
+            None => format!("the example of part {}", PARTS[0]),
+        };
+
+        let answer = fixtures.answers[i].as_deref().unwrap_or("?");
+
+        println!("{year}/{day:02} {part}: {input}, answer {answer}");
+    }
+
+    Ok(())
+}
+
+/// Runs a day on its example and its input whenever one of them or its sources change.
+fn watch(args: &[String]) -> io::Result<()> {
+    let (year, day) = (number(args.first())?, number(args.get(1))?);
+    let root = paths::root()?;
+
+    if !paths::day(&root, year, day).is_dir() {
+        return Err(io::Error::new(io::ErrorKind::NotFound, format!("{year}/{day:02} does not exist, see aoc new")));
+    }
+
+    watch::watch(&root, year, day)
+}
+
+/// Prints a synthetic input for a day of 2025, by default as large as a puzzle input.
+fn generate(args: &[String]) -> io::Result<()> {
+    let day = number(args.first())?;
+    let size = match aoc::args::value::<usize>("--size")? {
+        Some(size) => size,
+        None => generator::default_size(day)?,
+    };
+
+    let mut rng = Rng::from_args()?;
+
+    print!("{}", generator::generate(day, size, &mut rng)?);
+    eprintln!("day {day}: {size} {}, seed {}", generator::size_of(day)?, rng.seed());
+
+    Ok(())
+}
+
+/// Compares the parts which have a naive reference with it on small generated inputs: all of them, a day's or one.
+fn differential(args: &[String]) -> io::Result<()> {
+    let positional = positional(args);
+    let day = positional.first().map(|&arg| number(Some(arg))).transpose()?;
+    let part = positional.get(1).map(|part| part.as_str());
+    let cases = aoc::prop::cases_from_args(200)?;
+
+    let root = paths::root()?;
+    let mut rng = Rng::from_args()?;
+    let mut failed = 0;
+
+    for subject in &SUBJECTS {
+        if day.is_some_and(|day| day != subject.day) || part.is_some_and(|part| part != subject.part) {
+            continue;
+        }
+
+        let name = format!("{}/{:02} {}", differential::YEAR, subject.day, subject.part);
+        let executable = runner::build(&paths::day(&root, differential::YEAR, subject.day).join(subject.part))?;
+
+        match subject.check(&executable, &mut rng, cases) {
+            Ok(()) => println!("{name}: {cases} cases agree"),
+            Err(failure) => {
+                println!("{name}: case {} disagrees, {}", failure.case, failure.reason);
+                println!("shrunk in {} steps to:", failure.steps);
+                print!("{}", failure.shrunk);
+                failed += 1;
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(io::Error::other(format!("{failed} parts disagree, seed {}", rng.seed())));
+    }
+
+    Ok(())
+}
+
+/// Shows every registered day in a terminal dashboard.
+fn dashboard() -> io::Result<()> {
+    Dashboard::new(&paths::root()?)?.show()
+}
+
+/// Runs every part of a year, or of every registered year, and writes a page of results per year.
+fn report(args: &[String]) -> io::Result<()> {
+    let positional = positional(args);
+    let year = positional.first().map(|&arg| number(Some(arg))).transpose()?;
+    let root = paths::root()?;
+    let out = aoc::args::value::<PathBuf>("--out")?.unwrap_or_else(|| root.join("report"));
+
+    // By default the links are relative, for a report kept in the checkout
+    let source_url = match aoc::args::value::<String>("--source-url")? {
+        Some(url) => url,
+        None => match fs::create_dir_all(&out).and_then(|()| out.canonicalize())?.strip_prefix(&root) {
+            Ok(inside) => "../".repeat(inside.components().count()),
+            Err(_) => format!("file://{}/", root.display()),
+        },
+    };
+
+    let options = Options {
+        redact: args.iter().any(|arg| arg == "--redact"),
+        source_url,
+    };
+
+    let mut years: Vec<u32> = runner::registered(&root)?.into_iter().map(|(year, _)| year).collect();
+
+    years.dedup();
+    years.retain(|&y| year.is_none_or(|year| year == y));
+
+    if years.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::NotFound, "no registered days to report on"));
+    }
+
+    for year in years {
+        let entries = report::collect(&root, year, |entry| eprintln!("{year}/{:02} {} done", entry.day, entry.part))?;
+        let path = out.join(format!("{year}.html"));
+
+        fetch::write_atomically(&path, report::render(year, &entries, &options))?;
+        println!("{}", path.display());
+    }
+
+    Ok(())
+}
+
+/// Creates the key inputs are sealed with.
+fn key() -> io::Result<()> {
+    let path = Key::path().ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "set AOC_INPUT_KEY_FILE or HOME"))?;
+
+    seal::create_key(&path)?;
+    println!("{}", path.display());
+
+    Ok(())
+}
+
+/// Seals the inputs and accepted answers of every registered day, or of a year, or of a single day.
+fn seal(args: &[String]) -> io::Result<()> {
+    let year = args.first().map(|arg| number(Some(arg))).transpose()?;
+    let day = args.get(1).map(|arg| number(Some(arg))).transpose()?;
+
+    let root = paths::root()?;
+    let key = Key::load()?;
+
+    for (y, d) in runner::registered(&root)? {
+        if year.is_some_and(|year| year != y) || day.is_some_and(|day| day != d) {
+            continue;
+        }
+
+        for path in seal::seal_day(&root, &key, y, d)? {
+            println!("sealed {}", path.strip_prefix(&root).unwrap_or(&path).display());
+        }
+    }
+
+    Ok(())
+}
+
+/// Seals everything again under the key in the given file, creating that key if the file does not exist.
+fn rekey(args: &[String]) -> io::Result<()> {
+    let path = args.first().ok_or_else(usage)?;
+    let path = Path::new(path);
+
+    let root = paths::root()?;
+    let old = Key::load()?;
+    let new = if path.exists() { Key::read(path)? } else { seal::create_key(path)? };
+    let files = seal::rekey(&root, &old, &new)?;
+
+    println!("{} files sealed under the key in {}", files.len(), path.display());
+    println!("make it the key everywhere the old one was, including AOC_INPUT_KEY in CI");
+
+    Ok(())
+}
+
+/// Fails when a commit would publish an input or answers, or installs itself as the pre-commit hook.
+fn check_staged(args: &[String]) -> io::Result<()> {
+    let root = paths::root()?;
+
+    if args.iter().any(|arg| arg == "--install") {
+        println!("{}", seal::install_hook(&root)?.display());
+        return Ok(());
+    }
+
+    let flagged = seal::staged_plaintext(&root)?;
+
+    if flagged.is_empty() {
+        return Ok(());
+    }
+
+    for path in &flagged {
+        eprintln!("staged plaintext: {path}");
+    }
+
+    Err(io::Error::new(
+        io::ErrorKind::PermissionDenied,
+        "inputs and answers must not be published, unstage them and use `aoc seal`",
+    ))
+}
+
+fn main() -> io::Result<()> {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match args.first().map(String::as_str) {
+        Some("fetch") => fetch(&args[1..]),
+        Some("submit") => submit(&args[1..]),
+        Some("new") => new(&args[1..]),
+        Some("run") => run(&args[1..]),
+        Some("examples") => examples(&args[1..]),
+        Some("watch") => watch(&args[1..]),
+        Some("gen") => generate(&args[1..]),
+        Some("differential") => differential(&args[1..]),
+        Some("dashboard") => dashboard(),
+        Some("report") => report(&args[1..]),
+        Some("key") => key(),
+        Some("seal") => seal(&args[1..]),
+        Some("rekey") => rekey(&args[1..]),
+        Some("check-staged") => check_staged(&args[1..]),
+        _ => Err(usage()),
+    }
+}
@@ -0,0 +1,27 @@
+use std::{
+    env,
+    io,
+    path::{Path, PathBuf},
+};
+
+/// The checkout the command runs in: the closest directory above the current one holding the shared `aoc` crate.
+pub fn root() -> io::Result<PathBuf> {
+    let cwd = env::current_dir()?;
+
+    cwd.ancestors()
+        .find(|dir| dir.join("aoc/Cargo.toml").is_file())
+        .map(Path::to_path_buf)
+        .ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, format!("{} is not inside the repository", cwd.display()))
+        })
+}
+
+/// Git-ignored directory for downloads and request bookkeeping.
+pub fn cache(root: &Path) -> PathBuf {
+    root.join(".cache")
+}
+
+/// Directory of a day's crates, which read their input from `../input`.
+pub fn day(root: &Path, year: u32, day: u32) -> PathBuf {
+    root.join(year.to_string()).join(format!("{day:02}"))
+}
@@ -0,0 +1,244 @@
+//! Renders a year's results as a static HTML page that can be shared without running anything: every part's answer,
+//! time and peak memory, charts of the slowest and hungriest parts, and links to the sources.
+
+use std::{
+    fmt::Write as _,
+    io,
+    path::Path,
+    time::Duration,
+};
+
+use crate::{
+    paths,
+    runner::{self, PARTS},
+    submit::History,
+};
+
+/// Bars in each chart.
+const CHART_BARS: usize = 10;
+const CHART_WIDTH: usize = 640;
+const CHART_LABEL: usize = 90;
+/// Room right of the longest bar for its text.
+const CHART_TEXT: usize = 80;
+const CHART_ROW: usize = 22;
+
+const STYLE: &str = "body { font-family: sans-serif; margin: 2em auto; max-width: 60em; color: #222 }
+table { border-collapse: collapse; width: 100% }
+th, td { padding: 0.25em 0.75em; border-bottom: 1px solid #ddd; text-align: left }
+td.number { text-align: right; font-variant-numeric: tabular-nums }
+.accepted { color: #2a7 } .rejected, .failed { color: #c33 } .missing, .redacted { color: #999 }
+svg text { font-size: 12px; dominant-baseline: middle }
+svg rect { fill: #47a }";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// Registered, but the part has no crate yet.
+    Missing,
+    /// Did not build, or did not succeed, with what it printed on the way.
+    Failed(String),
+    Ran {
+        answer: String,
+        elapsed: Duration,
+        /// Peak resident memory, in bytes.
+        memory: u64,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    pub day: u32,
+    pub part: &'static str,
+    pub outcome: Outcome,
+    /// Whether the answer is the one the site accepted, when the part is solved.
+    pub accepted: Option<bool>,
+}
+
+/// Runs every part of the year's registered days on its input, calling `progress` after each.
+pub fn collect(root: &Path, year: u32, mut progress: impl FnMut(&Entry)) -> io::Result<Vec<Entry>> {
+    let cache = paths::cache(root);
+    let mut entries = Vec::new();
+
+    for (_, day) in runner::registered(root)?.into_iter().filter(|&(y, _)| y == year) {
+        let day_dir = paths::day(root, year, day);
+        let stored = runner::stored_answers(&day_dir)?.unwrap_or_default();
+
+        for (i, part) in PARTS.into_iter().enumerate() {
+            let part_dir = day_dir.join(part);
+            let outcome = if !part_dir.join("Cargo.toml").is_file() {
+                Outcome::Missing
+            } else {
+                match runner::timed(&part_dir, &[]) {
+                    Ok(timed) => match timed.answer() {
+                        Some(answer) => Outcome::Ran {
+                            answer: answer.to_owned(),
+                            elapsed: timed.elapsed,
+                            memory: timed.memory,
+                        },
+                        None => Outcome::Failed(timed.stderr),
+                    },
+                    Err(e) => Outcome::Failed(e.to_string()),
+                }
+            };
+
+            let history = History::load(History::path(&cache, year, day, i as u32 + 1))?;
+            let correct = history.correct().or(stored[i].as_deref());
+            let accepted = correct.map(|correct| match &outcome {
+                Outcome::Ran { answer, .. } => answer == correct,
+                _ => false,
+            });
+
+            let entry = Entry {
+                day,
+                part,
+                outcome,
+                accepted,
+            };
+
+            progress(&entry);
+            entries.push(entry);
+        }
+    }
+
+    Ok(entries)
+}
+
+pub struct Options {
+    /// Hides the answers, which the site asks not to publish, and what failing parts printed.
+    pub redact: bool,
+    /// Prefix of the links to the sources, `year/day/part/src/main.rs` being appended to it.
+    pub source_url: String,
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn format_memory(bytes: u64) -> String {
+    match bytes {
+        bytes if bytes < 1 << 20 => format!("{:.0} KiB", bytes as f64 / 1024.0),
+        bytes => format!("{:.1} MiB", bytes as f64 / (1 << 20) as f64),
+    }
+}
+
+/// A horizontal bar chart of the largest bars, each given as a label, a value and the value's text.
+fn chart(title: &str, mut bars: Vec<(String, f64, String)>) -> String {
+    bars.sort_by(|a, b| b.1.total_cmp(&a.1));
+    bars.truncate(CHART_BARS);
+
+    let max = bars.first().map_or(1.0, |bar| bar.1.max(f64::MIN_POSITIVE));
+    let span = (CHART_WIDTH - CHART_LABEL - CHART_TEXT) as f64;
+    let mut svg = format!(
+        "<h2>{title}</h2>\n<svg width=\"{CHART_WIDTH}\" height=\"{}\" role=\"img\" aria-label=\"{title}\">\n",
+        bars.len() * CHART_ROW
+    );
+
+    for (i, (label, value, text)) in bars.iter().enumerate() {
+        let y = i * CHART_ROW;
+        let width = (value / max * span).max(1.0);
+
+        let _ = writeln!(
+            svg,
+            "<text x=\"0\" y=\"{}\">{label}</text>\
+             <rect x=\"{CHART_LABEL}\" y=\"{}\" width=\"{width:.1}\" height=\"{}\"/>\
+             <text x=\"{:.1}\" y=\"{}\">{text}</text>",
+            y + CHART_ROW / 2,
+            y + 3,
+            CHART_ROW - 6,
+            CHART_LABEL as f64 + width + 6.0,
+            y + CHART_ROW / 2,
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// The page for a year.
+pub fn render(year: u32, entries: &[Entry], options: &Options) -> String {
+    let ran: Vec<(String, Duration, u64)> = entries
+        .iter()
+        .filter_map(|entry| match &entry.outcome {
+            Outcome::Ran { elapsed, memory, .. } => {
+                Some((format!("{:02} {}", entry.day, entry.part), *elapsed, *memory))
+            }
+            _ => None,
+        })
+        .collect();
+
+    let accepted = entries.iter().filter(|entry| entry.accepted == Some(true)).count();
+    let total: Duration = ran.iter().map(|(_, elapsed, _)| *elapsed).sum();
+
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>Advent of Code {year}</title>\n\
+         <style>\n{STYLE}\n</style>\n</head>\n<body>\n<h1>Advent of Code {year}</h1>\n\
+         <p>{accepted} of {} parts accepted, {} of {} parts ran in {} altogether.</p>\n",
+        entries.len(),
+        ran.len(),
+        entries.len(),
+        runner::format_elapsed(total),
+    );
+
+    html.push_str(&chart(
+        "Slowest parts",
+        ran.iter()
+            .map(|(label, elapsed, _)| (label.clone(), elapsed.as_secs_f64(), runner::format_elapsed(*elapsed)))
+            .collect(),
+    ));
+    html.push_str(&chart(
+        "Most memory",
+        ran.iter().map(|(label, _, memory)| (label.clone(), *memory as f64, format_memory(*memory))).collect(),
+    ));
+
+    html.push_str(
+        "<h2>Parts</h2>\n<table>\n<thead><tr><th>day</th><th>part</th><th>answer</th><th>accepted</th>\
+         <th>time</th><th>memory</th><th>source</th></tr></thead>\n<tbody>\n",
+    );
+
+    for entry in entries {
+        let (answer, elapsed, memory) = match &entry.outcome {
+            Outcome::Missing => ("<span class=\"missing\">not written</span>".to_owned(), String::new(), String::new()),
+            // What a part printed before failing may hold its answer or its input
+            Outcome::Failed(_) if options.redact => {
+                ("<span class=\"failed\">failed</span>".to_owned(), String::new(), String::new())
+            }
+            Outcome::Failed(output) => (
+                format!("<details><summary class=\"failed\">failed</summary><pre>{}</pre></details>", escape(output)),
+                String::new(),
+                String::new(),
+            ),
+            Outcome::Ran { answer, elapsed, memory } => (
+                if options.redact {
+                    "<span class=\"redacted\">redacted</span>".to_owned()
+                } else {
+                    format!("<code>{}</code>", escape(answer))
+                },
+                runner::format_elapsed(*elapsed),
+                format_memory(*memory),
+            ),
+        };
+
+        let accepted = match entry.accepted {
+            Some(true) => "<span class=\"accepted\">yes</span>",
+            Some(false) => "<span class=\"rejected\">no</span>",
+            None => "",
+        };
+
+        let source = if entry.outcome == Outcome::Missing {
+            String::new()
+        } else {
+            let path = format!("{year}/{:02}/{}/src/main.rs", entry.day, entry.part);
+
+            format!("<a href=\"{}{path}\">{path}</a>", escape(&options.source_url))
+        };
+
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td>{answer}</td><td>{accepted}</td><td class=\"number\">{elapsed}</td>\
+             <td class=\"number\">{memory}</td><td>{source}</td></tr>",
+            entry.day, entry.part,
+        );
+    }
+
+    html.push_str("</tbody>\n</table>\n</body>\n</html>\n");
+    html
+}
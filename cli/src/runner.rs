@@ -0,0 +1,600 @@
+//! Runs the days' solvers. Days are listed in the registry file at the root of the checkout, one `year/day` per
+//! line, and each has a crate per part which reads its input from `../input`. Parts only read plaintext: a sealed
+//! input is opened here and handed to them.
+
+use std::{
+    env,
+    fs,
+    io::{self, Read, Write},
+    mem,
+    os::unix::fs::{DirBuilderExt, OpenOptionsExt},
+    path::{Path, PathBuf},
+    process::{self, Child, Command, Stdio},
+    sync::{
+        LazyLock,
+        atomic::{AtomicUsize, Ordering},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use aoc::{input, vault};
+use regex::Regex;
+
+use crate::{fetch, paths};
+
+static EXECUTABLE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#""reason":"compiler-artifact".*"executable":"((?:[^"\\]|\\.)*)""#).unwrap());
+
+pub const REGISTRY: &str = "days";
+
+/// Crate directories of the two parts.
+pub const PARTS: [&str; 2] = ["a", "b"];
+
+/// Days in the registry, in order.
+pub fn registered(root: &Path) -> io::Result<Vec<(u32, u32)>> {
+    let path = root.join(REGISTRY);
+    let text = match fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+
+    let mut days = Vec::new();
+
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let day = line
+            .split_once('/')
+            .and_then(|(year, day)| Some((year.parse().ok()?, day.parse().ok()?)))
+            .ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidData, format!("{}:{}: expected `year/day`", path.display(), i + 1))
+            })?;
+
+        days.push(day);
+    }
+
+    days.sort_unstable();
+    days.dedup();
+
+    Ok(days)
+}
+
+/// Adds a day to the registry, keeping it sorted.
+pub fn register(root: &Path, year: u32, day: u32) -> io::Result<()> {
+    let mut days = registered(root)?;
+
+    if days.contains(&(year, day)) {
+        return Ok(());
+    }
+
+    days.push((year, day));
+    days.sort_unstable();
+
+    let mut text = String::from("# Days run by `aoc run`, added by `aoc new`\n");
+
+    for (year, day) in days {
+        text.push_str(&format!("{year}/{day:02}\n"));
+    }
+
+    fetch::write_atomically(&root.join(REGISTRY), &text)
+}
+
+/// The answer a part prints: the last line of its output.
+fn answer(command: &mut Command, part_dir: &Path) -> io::Result<String> {
+    let output = command.stderr(Stdio::inherit()).output()?;
+
+    if !output.status.success() {
+        return Err(io::Error::other(format!("{} failed with {}", part_dir.display(), output.status)));
+    }
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .rev()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_owned)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, format!("{} printed nothing", part_dir.display())))
+}
+
+/// Prefix of the scratch directories, followed by the process id and a count.
+const SCRATCH_PREFIX: &str = "aoc-run-";
+
+/// A scratch directory with a `run` directory to run parts from, their input going next to it. The input may have
+/// been opened from its sealed copy, so only the user can read the directory, and it is removed when dropped, however
+/// the run ended.
+struct Scratch {
+    dir: PathBuf,
+}
+
+impl Scratch {
+    fn new() -> io::Result<Self> {
+        static RUNS: AtomicUsize = AtomicUsize::new(0);
+
+        sweep_scratch();
+
+        let run = RUNS.fetch_add(1, Ordering::Relaxed);
+        let dir = env::temp_dir().join(format!("{SCRATCH_PREFIX}{}-{run}", process::id()));
+
+        fs::DirBuilder::new().mode(0o700).create(&dir)?;
+
+        let scratch = Scratch { dir };
+
+        fs::create_dir(scratch.run_dir())?;
+        Ok(scratch)
+    }
+
+    fn run_dir(&self) -> PathBuf {
+        self.dir.join("run")
+    }
+
+    /// Writes the input the parts read as `../input`, readable by the user alone.
+    fn write_input(&self, text: &[u8]) -> io::Result<()> {
+        fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(0o600)
+            .open(self.dir.join("input"))?
+            .write_all(text)
+    }
+}
+
+impl Drop for Scratch {
+    fn drop(&mut self) {
+        // Nothing is left to do about a directory that cannot be removed, the next sweep tries again
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// Removes the scratch directories of processes that are gone, which a run interrupted before its [`Scratch`] was
+/// dropped leaves behind.
+fn sweep_scratch() {
+    let Ok(entries) = fs::read_dir(env::temp_dir()) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let Some(pid) = name
+            .to_str()
+            .and_then(|name| name.strip_prefix(SCRATCH_PREFIX))
+            .and_then(|rest| rest.split_once('-'))
+            .and_then(|(pid, _)| pid.parse::<libc::pid_t>().ok())
+        else {
+            continue;
+        };
+
+        // SAFETY: signal 0 only checks whether the process exists
+        let gone = unsafe { libc::kill(pid, 0) } != 0 && io::Error::last_os_error().raw_os_error() == Some(libc::ESRCH);
+
+        if gone {
+            let _ = fs::remove_dir_all(entry.path());
+        }
+    }
+}
+
+/// Where a part runs on its day's input: its own directory, or, when only the sealed copy of the input is checked in,
+/// a scratch directory holding it opened, as parts do not open sealed files. The scratch directory is returned too,
+/// to be kept until the run is over.
+fn input_dir(part_dir: &Path) -> io::Result<(PathBuf, Option<Scratch>)> {
+    let input = part_dir.join("../input");
+
+    if input.exists() || !vault::sealed_path(&input).is_file() {
+        return Ok((part_dir.to_owned(), None));
+    }
+
+    let scratch = Scratch::new()?;
+
+    scratch.write_input(input::read(&input)?.as_bytes())?;
+    Ok((scratch.run_dir(), Some(scratch)))
+}
+
+/// Runs a part on its day's input.
+pub fn run(part_dir: &Path) -> io::Result<String> {
+    let (cwd, _scratch) = input_dir(part_dir)?;
+    let manifest = part_dir.join("Cargo.toml");
+
+    answer(
+        Command::new("cargo")
+            .args(["run", "--release", "--quiet", "--manifest-path"])
+            .arg(&manifest)
+            .current_dir(&cwd),
+        part_dir,
+    )
+}
+
+/// Runs a part on another input, from a scratch directory whose `../input` is a copy of it.
+pub fn run_on(part_dir: &Path, input: &Path) -> io::Result<String> {
+    let scratch = Scratch::new()?;
+
+    scratch.write_input(&fs::read(input)?)?;
+
+    let manifest = part_dir.join("Cargo.toml");
+
+    answer(
+        Command::new("cargo")
+            .args(["run", "--release", "--quiet", "--manifest-path"])
+            .arg(&manifest)
+            .current_dir(scratch.run_dir()),
+        part_dir,
+    )
+}
+
+/// Builds a part, returning its executable. Compiler errors are returned rather than shown.
+pub fn build(part_dir: &Path) -> io::Result<PathBuf> {
+    let output = Command::new("cargo")
+        .args(["build", "--release", "--quiet", "--message-format=json-render-diagnostics"])
+        .current_dir(part_dir)
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+
+        return Err(io::Error::other(format!("{} does not build\n{stderr}", part_dir.display())));
+    }
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .rev()
+        .find_map(|line| EXECUTABLE_REGEX.captures(line))
+        .map(|executable| PathBuf::from(executable[1].replace("\\\\", "\\")))
+        .ok_or_else(|| io::Error::other(format!("{} built no executable", part_dir.display())))
+}
+
+/// Runs a part built by [`build`] on `input`, like [`run_on`] but without cargo. What the part prints to stderr is
+/// returned with its failure rather than shown.
+pub fn run_built(executable: &Path, input: &str) -> io::Result<String> {
+    let scratch = Scratch::new()?;
+
+    scratch.write_input(input.as_bytes())?;
+
+    let output = Command::new(executable).current_dir(scratch.run_dir()).output()?;
+
+    drop(scratch);
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+
+        return Err(io::Error::other(format!("failed with {}\n{}", output.status, stderr.trim_end())));
+    }
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .rev()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_owned)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, format!("{} printed nothing", executable.display())))
+}
+
+/// A part run on its own, outside of cargo, so its time and memory are the solver's alone.
+pub struct Timed {
+    pub stdout: String,
+    pub stderr: String,
+    pub elapsed: Duration,
+    /// Peak resident memory, in bytes.
+    pub memory: u64,
+    pub success: bool,
+}
+
+impl Timed {
+    /// The answer: the last line printed, if the part succeeded.
+    pub fn answer(&self) -> Option<&str> {
+        if !self.success {
+            return None;
+        }
+
+        self.stdout.lines().rev().map(str::trim).find(|line| !line.is_empty())
+    }
+}
+
+/// A run time, in the unit that suits it.
+pub fn format_elapsed(elapsed: Duration) -> String {
+    match elapsed.as_micros() {
+        micros if micros < 1000 => format!("{micros}µs"),
+        micros if micros < 1_000_000 => format!("{:.1}ms", micros as f64 / 1e3),
+        micros => format!("{:.2}s", micros as f64 / 1e6),
+    }
+}
+
+/// Waits for a child like `Child::wait`, also returning its peak resident memory in bytes, which the standard
+/// library does not report.
+fn wait_with_memory(child: &Child) -> io::Result<(bool, u64)> {
+    let mut status = 0;
+    // SAFETY: `rusage` is plain data, for which zeroes are valid
+    let mut usage: libc::rusage = unsafe { mem::zeroed() };
+
+    loop {
+        // SAFETY: the child was not waited for yet, and the pointers are to live locals
+        if unsafe { libc::wait4(child.id() as libc::pid_t, &mut status, 0, &mut usage) } >= 0 {
+            break;
+        }
+
+        let e = io::Error::last_os_error();
+
+        if e.kind() != io::ErrorKind::Interrupted {
+            return Err(e);
+        }
+    }
+
+    let success = libc::WIFEXITED(status) && libc::WEXITSTATUS(status) == 0;
+
+    // Linux counts kilobytes
+    Ok((success, usage.ru_maxrss as u64 * 1024))
+}
+
+fn read_all(mut pipe: impl Read + Send + 'static) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut bytes = Vec::new();
+        let _ = pipe.read_to_end(&mut bytes);
+
+        String::from_utf8_lossy(&bytes).into_owned()
+    })
+}
+
+/// Builds a part, then runs it on its day's input with `args`.
+pub fn timed(part_dir: &Path, args: &[&str]) -> io::Result<Timed> {
+    let executable = build(part_dir)?;
+    let (cwd, scratch) = input_dir(part_dir)?;
+    let start = Instant::now();
+    let child = Command::new(executable)
+        .args(args)
+        .current_dir(&cwd)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn();
+    let waited = child.and_then(|mut child| {
+        // Both pipes are drained while the part runs, so neither can fill up and block it
+        let stdout = read_all(child.stdout.take().expect("stdout is piped"));
+        let stderr = read_all(child.stderr.take().expect("stderr is piped"));
+
+        wait_with_memory(&child).map(|waited| (stdout, stderr, waited))
+    });
+    let elapsed = start.elapsed();
+
+    drop(scratch);
+
+    let (stdout, stderr, (success, memory)) = waited?;
+
+    Ok(Timed {
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+        elapsed,
+        memory,
+        success,
+    })
+}
+
+/// A day's example from the puzzle text, kept as `example` next to its parts, and the answers the text gives for it
+/// in `example.answers`, where `?` marks an answer not known yet. Part two reads `example.b` instead when its text
+/// brings an example of its own.
+pub struct Example {
+    pub inputs: [PathBuf; 2],
+    pub answers: [Option<String>; 2],
+}
+
+impl Example {
+    pub fn input_path(day_dir: &Path) -> PathBuf {
+        day_dir.join("example")
+    }
+
+    /// Part two's own example, when it has one.
+    pub fn second_input_path(day_dir: &Path) -> PathBuf {
+        day_dir.join("example.b")
+    }
+
+    pub fn answers_path(day_dir: &Path) -> PathBuf {
+        day_dir.join("example.answers")
+    }
+
+    /// The day's example, if it has one.
+    pub fn load(day_dir: &Path) -> io::Result<Option<Self>> {
+        let input = Example::input_path(day_dir);
+        let second = Example::second_input_path(day_dir);
+        let path = Example::answers_path(day_dir);
+
+        if !input.is_file() {
+            return Ok(None);
+        }
+
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e),
+        };
+
+        let answers = parse_answers(&text, &path)?;
+        let second = if second.is_file() { second } else { input.clone() };
+
+        Ok(Some(Example {
+            inputs: [input, second],
+            answers,
+        }))
+    }
+
+    pub fn write_answers(day_dir: &Path, answers: &[Option<String>; 2]) -> io::Result<()> {
+        let text = answers_text("Answers the puzzle text gives for the example, `?` while unknown", answers);
+
+        fetch::write_atomically(&Example::answers_path(day_dir), text)
+    }
+}
+
+/// Parses `a = answer` lines, `?` marking an answer not known yet.
+fn parse_answers(text: &str, path: &Path) -> io::Result<[Option<String>; 2]> {
+    let mut answers = [None, None];
+
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let invalid = || {
+            let message = format!("{}:{}: expected `a = answer`", path.display(), i + 1);
+
+            io::Error::new(io::ErrorKind::InvalidData, message)
+        };
+
+        let (part, answer) = line.split_once('=').ok_or_else(invalid)?;
+        let part = PARTS.iter().position(|&p| p == part.trim()).ok_or_else(invalid)?;
+        let answer = answer.trim();
+
+        answers[part] = (answer != "?").then(|| answer.to_owned());
+    }
+
+    Ok(answers)
+}
+
+/// The text [`parse_answers`] reads, after a comment.
+pub fn answers_text(comment: &str, answers: &[Option<String>; 2]) -> String {
+    let mut text = format!("# {comment}\n");
+
+    for (part, answer) in PARTS.iter().zip(answers) {
+        text.push_str(&format!("{part} = {}\n", answer.as_deref().unwrap_or("?")));
+    }
+
+    text
+}
+
+/// Whether the day's input is there, in plaintext or sealed.
+pub fn has_input(day_dir: &Path) -> bool {
+    let path = day_dir.join("input");
+
+    path.is_file() || vault::sealed_path(&path).is_file()
+}
+
+/// The accepted answers on the real input, which are kept sealed next to it, see [`aoc::vault`].
+pub fn answers_path(day_dir: &Path) -> PathBuf {
+    day_dir.join("answers")
+}
+
+/// The day's accepted answers, if they are stored.
+pub fn stored_answers(day_dir: &Path) -> io::Result<Option<[Option<String>; 2]>> {
+    let path = answers_path(day_dir);
+
+    if !path.is_file() && !vault::sealed_path(&path).is_file() {
+        return Ok(None);
+    }
+
+    parse_answers(&input::read(&path)?, &path).map(Some)
+}
+
+/// The day's accepted answers as sealed under `key`, if they are.
+pub fn sealed_answers(day_dir: &Path, key: &vault::Key) -> io::Result<Option<[Option<String>; 2]>> {
+    let path = answers_path(day_dir);
+
+    if !vault::sealed_path(&path).is_file() {
+        return Ok(None);
+    }
+
+    parse_answers(&vault::open_file(key, &path)?, &path).map(Some)
+}
+
+/// What a part printed, checked against the expected answer when there is one.
+pub enum Run {
+    Answer(String),
+    Passed(String),
+    Failed { expected: String, actual: String },
+}
+
+fn check(actual: String, expected: Option<&String>) -> Run {
+    match expected {
+        None => Run::Answer(actual),
+        Some(expected) if *expected == actual => Run::Passed(actual),
+        Some(expected) => Run::Failed {
+            expected: expected.clone(),
+            actual,
+        },
+    }
+}
+
+/// Runs the parts of a day, on its example when `example` is set, in part order. Answers on the input are checked
+/// against the stored ones.
+pub fn run_day(root: &Path, year: u32, day: u32, example: bool) -> io::Result<Vec<(&'static str, Run)>> {
+    let day_dir = paths::day(root, year, day);
+    let example = if example {
+        let example = Example::load(&day_dir)?.ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, format!("{year}/{day:02} has no example"))
+        })?;
+
+        Some(example)
+    } else {
+        None
+    };
+
+    let stored = if example.is_none() { stored_answers(&day_dir)? } else { None };
+    let mut runs = Vec::new();
+
+    for (i, part) in PARTS.into_iter().enumerate() {
+        let part_dir = day_dir.join(part);
+
+        if !part_dir.join("Cargo.toml").is_file() {
+            continue;
+        }
+
+        let run = match &example {
+            Some(example) => check(run_on(&part_dir, &example.inputs[i])?, example.answers[i].as_ref()),
+            None => check(run(&part_dir)?, stored.as_ref().and_then(|stored| stored[i].as_ref())),
+        };
+
+        runs.push((part, run));
+    }
+
+    Ok(runs)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::os::unix::fs::PermissionsExt;
+
+    use super::*;
+
+    fn mode(path: &Path) -> u32 {
+        fs::metadata(path).unwrap().permissions().mode() & 0o777
+    }
+
+    #[test]
+    fn keeps_the_scratch_input_private_and_removes_it() {
+        let scratch = Scratch::new().unwrap();
+        let dir = scratch.dir.clone();
+
+        scratch.write_input(b"1\n2\n").unwrap();
+
+        assert_eq!(mode(&dir), 0o700);
+        assert_eq!(mode(&dir.join("input")), 0o600);
+        assert_eq!(fs::read_to_string(scratch.run_dir().join("../input")).unwrap(), "1\n2\n");
+
+        let unwound = std::panic::catch_unwind(move || {
+            let _scratch = scratch;
+
+            panic!("the run failed");
+        });
+
+        assert!(unwound.is_err());
+        assert!(!dir.exists(), "the scratch directory is removed however the run ends");
+    }
+
+    #[test]
+    fn sweeps_the_scratch_of_processes_that_are_gone() {
+        let mut child = Command::new("true").spawn().unwrap();
+
+        child.wait().unwrap();
+
+        let left = env::temp_dir().join(format!("{SCRATCH_PREFIX}{}-0", child.id()));
+        let live = Scratch::new().unwrap();
+
+        fs::create_dir_all(left.join("run")).unwrap();
+        fs::write(left.join("input"), "1\n").unwrap();
+        sweep_scratch();
+
+        assert!(!left.exists(), "an interrupted run's input is removed");
+        assert!(live.dir.exists(), "a running process keeps its own");
+    }
+}
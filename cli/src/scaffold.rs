@@ -0,0 +1,120 @@
+//! Creates the crates of a new day from a template matching the shape of its input.
+
+use std::{
+    fs,
+    io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crate::{
+    fetch,
+    paths,
+    runner::{self, Example, PARTS},
+};
+
+const LINES: &str = include_str!("../templates/lines.rs");
+const GRID: &str = include_str!("../templates/grid.rs");
+const SECTIONS: &str = include_str!("../templates/sections.rs");
+
+/// Files every year directory ignores: the inputs and their answers, which must not be published, and the build output.
+const GITIGNORE: &str = "input\nanswers\ntarget/\n*.partial\n";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Template {
+    /// One record per line, parsed with `FromStr`.
+    Lines,
+    /// A rectangle of characters.
+    Grid,
+    /// Blank-line separated sections, like the shapes and regions of 2025 day 12.
+    Sections,
+}
+
+impl Template {
+    fn source(self) -> &'static str {
+        match self {
+            Template::Lines => LINES,
+            Template::Grid => GRID,
+            Template::Sections => SECTIONS,
+        }
+    }
+}
+
+impl FromStr for Template {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "lines" => Ok(Template::Lines),
+            "grid" => Ok(Template::Grid),
+            "sections" => Ok(Template::Sections),
+            _ => Err(format!("unknown template '{s}', expected lines, grid or sections")),
+        }
+    }
+}
+
+/// Every part's package is called `a`, the crate directory telling the parts apart.
+const MANIFEST: &str = r#"[package]
+name = "a"
+version = "0.1.0"
+edition = "2024"
+
+[dependencies]
+aoc = { path = "../../../aoc" }
+"#;
+
+/// Writes a new file, refusing to replace one.
+fn create(path: &Path, text: &str, created: &mut Vec<PathBuf>) -> io::Result<()> {
+    if path.exists() {
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("{} already exists", path.display())));
+    }
+
+    fetch::write_atomically(path, text)?;
+    created.push(path.to_path_buf());
+
+    Ok(())
+}
+
+/// Creates both parts of a day, each with a test solving the example, and an empty example with unknown answers, and
+/// registers the day with the runner. Returns the files created.
+pub fn new_day(root: &Path, year: u32, day: u32, template: Template) -> io::Result<Vec<PathBuf>> {
+    fetch::check_day(year, day)?;
+
+    let day_dir = paths::day(root, year, day);
+
+    for part in PARTS {
+        if day_dir.join(part).exists() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{} already exists", day_dir.join(part).display()),
+            ));
+        }
+    }
+
+    let rustfmt = fs::read_to_string(root.join("aoc/rustfmt.toml"))?;
+    let mut created = Vec::new();
+
+    for part in PARTS {
+        let part_dir = day_dir.join(part);
+
+        create(&part_dir.join("Cargo.toml"), MANIFEST, &mut created)?;
+        create(&part_dir.join("rustfmt.toml"), &rustfmt, &mut created)?;
+        create(&part_dir.join("src/main.rs"), template.source(), &mut created)?;
+    }
+
+    if !Example::input_path(&day_dir).exists() {
+        create(&Example::input_path(&day_dir), "", &mut created)?;
+        Example::write_answers(&day_dir, &[None, None])?;
+        created.push(Example::answers_path(&day_dir));
+    }
+
+    let gitignore = root.join(year.to_string()).join(".gitignore");
+
+    if !gitignore.exists() {
+        create(&gitignore, GITIGNORE, &mut created)?;
+    }
+
+    runner::register(root, year, day)?;
+
+    Ok(created)
+}
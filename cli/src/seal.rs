@@ -0,0 +1,224 @@
+//! Keeps the inputs and their accepted answers in the repository without publishing them: they are checked in sealed,
+//! see [`aoc::vault`], and a pre-commit check refuses plaintext ones.
+
+use std::{
+    collections::HashSet,
+    fs,
+    io::{self, Write},
+    os::unix::fs::OpenOptionsExt,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use aoc::vault::{self, Key};
+
+use crate::{
+    fetch,
+    paths,
+    runner,
+    submit::History,
+};
+
+/// Files of a day which are only ever checked in sealed.
+pub const SEALED: [&str; 2] = ["input", "answers"];
+
+/// Creates a key at `path`, readable by its owner only. An existing key is never replaced.
+pub fn create_key(path: &Path) -> io::Result<Key> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let key = Key::generate();
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(path)
+        .map_err(|e| io::Error::new(e.kind(), format!("{}: {e}", path.display())))?;
+
+    writeln!(file, "{}", key.to_hex())?;
+
+    Ok(key)
+}
+
+/// Seals `plaintext` as the sealed copy of `path`, unless that copy already holds it: sealing again would change
+/// every byte, and the file with it.
+fn seal_text(key: &Key, path: &Path, plaintext: &str) -> io::Result<Option<PathBuf>> {
+    let sealed = vault::sealed_path(path);
+
+    if let Ok(bytes) = fs::read(&sealed)
+        && vault::open(key, &bytes).is_ok_and(|opened| opened == plaintext.as_bytes())
+    {
+        return Ok(None);
+    }
+
+    fetch::write_atomically(&sealed, vault::seal(key, plaintext.as_bytes()))?;
+
+    Ok(Some(sealed))
+}
+
+/// Seals a day's input and the answers the site accepted for it, returning the sealed files that changed.
+pub fn seal_day(root: &Path, key: &Key, year: u32, day: u32) -> io::Result<Vec<PathBuf>> {
+    let day_dir = paths::day(root, year, day);
+    let input = day_dir.join("input");
+    let mut sealed = Vec::new();
+
+    if input.is_file() {
+        sealed.extend(seal_text(key, &input, &fs::read_to_string(&input)?)?);
+    }
+
+    let cache = paths::cache(root);
+    let mut answers = [None, None];
+
+    for (i, answer) in answers.iter_mut().enumerate() {
+        *answer = History::load(History::path(&cache, year, day, i as u32 + 1))?.correct().map(str::to_owned);
+    }
+
+    // Answers already sealed stay, in case this checkout never submitted them
+    if let Some(stored) = runner::sealed_answers(&day_dir, key)? {
+        for (answer, stored) in answers.iter_mut().zip(stored) {
+            if answer.is_none() {
+                *answer = stored;
+            }
+        }
+    }
+
+    if answers.iter().any(Option::is_some) {
+        let text = runner::answers_text("Answers the site accepted for the input, `?` while unknown", &answers);
+
+        sealed.extend(seal_text(key, &runner::answers_path(&day_dir), &text)?);
+    }
+
+    Ok(sealed)
+}
+
+/// Sealed files of the registered days.
+pub fn sealed_files(root: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+
+    for (year, day) in runner::registered(root)? {
+        let day_dir = paths::day(root, year, day);
+
+        for name in SEALED {
+            let sealed = vault::sealed_path(&day_dir.join(name));
+
+            if sealed.is_file() {
+                files.push(sealed);
+            }
+        }
+    }
+
+    Ok(files)
+}
+
+/// Seals every sealed file again under `new`. All of them are opened before any is rewritten, so a wrong `old` key
+/// changes nothing.
+pub fn rekey(root: &Path, old: &Key, new: &Key) -> io::Result<Vec<PathBuf>> {
+    let mut opened = Vec::new();
+
+    for path in sealed_files(root)? {
+        let plaintext = vault::open(old, &fs::read(&path)?)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {e}", path.display())))?;
+
+        opened.push((path, plaintext));
+    }
+
+    for (path, plaintext) in &opened {
+        fetch::write_atomically(path, vault::seal(new, plaintext))?;
+    }
+
+    Ok(opened.into_iter().map(|(path, _)| path).collect())
+}
+
+fn git(root: &Path, args: &[&str]) -> io::Result<String> {
+    let output = Command::new("git").args(args).current_dir(root).stderr(Stdio::inherit()).output()?;
+
+    if !output.status.success() {
+        return Err(io::Error::other(format!("git {} failed with {}", args.join(" "), output.status)));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Staged files which would publish an input or answers: any file named like one, any download left unfinished by
+/// [`fetch::write_atomically`], and any copy of a local input under another name.
+pub fn staged_plaintext(root: &Path) -> io::Result<Vec<String>> {
+    let staged = git(root, &["diff", "--cached", "--name-only", "-z", "--diff-filter=ACMR"])?;
+    let staged: Vec<&str> = staged.split('\0').filter(|path| !path.is_empty()).collect();
+
+    if staged.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut inputs = Vec::new();
+
+    for (year, day) in runner::registered(root)? {
+        let input = paths::day(root, year, day).join("input");
+
+        if input.is_file() {
+            inputs.push(input.to_string_lossy().into_owned());
+        }
+    }
+
+    let mut secret = HashSet::new();
+
+    if !inputs.is_empty() {
+        let mut args = vec!["hash-object", "--"];
+
+        args.extend(inputs.iter().map(String::as_str));
+        secret.extend(git(root, &args)?.lines().map(str::to_owned));
+    }
+
+    let mut args = vec!["ls-files", "--stage", "-z", "--"];
+
+    args.extend(&staged);
+
+    let mut flagged = Vec::new();
+
+    // Entries are `mode blob stage\tpath`
+    for entry in git(root, &args)?.split('\0').filter(|entry| !entry.is_empty()) {
+        let Some((info, path)) = entry.split_once('\t') else {
+            continue;
+        };
+
+        let blob = info.split(' ').nth(1).unwrap_or_default();
+        let name = Path::new(path).file_name().and_then(|name| name.to_str()).unwrap_or_default();
+
+        if SEALED.contains(&name) || name.ends_with(".partial") || secret.contains(blob) {
+            flagged.push(path.to_owned());
+        }
+    }
+
+    Ok(flagged)
+}
+
+/// Hook running the staged check before every commit.
+const HOOK: &str = "#!/bin/sh
+# Refuses commits publishing puzzle inputs or answers, installed by `aoc check-staged --install`
+exec cargo run --quiet --manifest-path cli/Cargo.toml --bin aoc -- check-staged
+";
+
+/// Installs the staged check as the repository's pre-commit hook, unless there is a hook already.
+pub fn install_hook(root: &Path) -> io::Result<PathBuf> {
+    let hooks = PathBuf::from(git(root, &["rev-parse", "--git-path", "hooks"])?.trim());
+    let hook = root.join(hooks).join("pre-commit");
+
+    if let Ok(existing) = fs::read_to_string(&hook) {
+        if existing == HOOK {
+            return Ok(hook);
+        }
+
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists, add `aoc check-staged` to it", hook.display()),
+        ));
+    }
+
+    if let Some(dir) = hook.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    fs::OpenOptions::new().write(true).create_new(true).mode(0o755).open(&hook)?.write_all(HOOK.as_bytes())?;
+
+    Ok(hook)
+}
@@ -0,0 +1,299 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    fs,
+    io,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::LazyLock,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use regex::Regex;
+
+use crate::fetch::{self, Client};
+
+static TAG_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"<[^>]*>").unwrap());
+static LEFT_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?:(\d+)h )?(?:(\d+)m )?(\d+)s left to wait").unwrap());
+static COOLDOWN_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"wait (one|two|three|four|five|ten|\d+) minutes? before trying again").unwrap());
+
+/// How the site judged an answer it accepted to look at.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Judgement {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong without a hint, as the site says of answers far off or not numbers.
+    Wrong,
+}
+
+impl Display for Judgement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Judgement::Correct => write!(f, "correct"),
+            Judgement::TooHigh => write!(f, "too-high"),
+            Judgement::TooLow => write!(f, "too-low"),
+            Judgement::Wrong => write!(f, "wrong"),
+        }
+    }
+}
+
+impl FromStr for Judgement {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Judgement::Correct),
+            "too-high" => Ok(Judgement::TooHigh),
+            "too-low" => Ok(Judgement::TooLow),
+            "wrong" => Ok(Judgement::Wrong),
+            _ => Err(format!("unknown judgement '{s}'")),
+        }
+    }
+}
+
+/// What the site made of a submission.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Judged(Judgement),
+    /// Submitted too soon after the last one, the answer not looked at.
+    TooSoon,
+    /// The part was solved already.
+    Solved,
+    /// A page none of the above, given as its text.
+    Unknown(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Response {
+    pub verdict: Verdict,
+    /// How long the site wants before the next submission.
+    pub wait: Option<Duration>,
+    pub text: String,
+}
+
+/// Reads the site's answer page, which explains its verdict in the prose of its `<article>`.
+pub fn parse_response(html: &str) -> Response {
+    let article = match (html.find("<article"), html.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &html[start..end],
+        _ => html,
+    };
+
+    let text = TAG_REGEX.replace_all(article, "");
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    let verdict = if text.contains("That's the right answer") {
+        Verdict::Judged(Judgement::Correct)
+    } else if text.contains("answer is too high") {
+        Verdict::Judged(Judgement::TooHigh)
+    } else if text.contains("answer is too low") {
+        Verdict::Judged(Judgement::TooLow)
+    } else if text.contains("That's not the right answer") {
+        Verdict::Judged(Judgement::Wrong)
+    } else if text.contains("You gave an answer too recently") {
+        Verdict::TooSoon
+    } else if text.contains("Did you already complete it") {
+        Verdict::Solved
+    } else {
+        Verdict::Unknown(text.clone())
+    };
+
+    let wait = if let Some(captures) = LEFT_REGEX.captures(&text) {
+        let part = |i: usize| captures.get(i).map_or(0, |m| m.as_str().parse::<u64>().unwrap_or(0));
+
+        Some(Duration::from_secs(3600 * part(1) + 60 * part(2) + part(3)))
+    } else {
+        COOLDOWN_REGEX.captures(&text).map(|captures| {
+            let minutes = match &captures[1] {
+                "one" => 1,
+                "two" => 2,
+                "three" => 3,
+                "four" => 4,
+                "five" => 5,
+                "ten" => 10,
+                n => n.parse().unwrap_or(1),
+            };
+
+            Duration::from_secs(60 * minutes)
+        })
+    };
+
+    Response { verdict, wait, text }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    /// Seconds since the Unix epoch.
+    pub time: u64,
+    pub answer: String,
+    pub judgement: Judgement,
+}
+
+/// Answers submitted for one part and how they were judged, kept so an answer the site is known to reject is never
+/// sent. Stored as `time judgement answer` lines, with `time wait` lines for when the site allows the next try.
+pub struct History {
+    path: PathBuf,
+    pub entries: Vec<Entry>,
+    /// No submission is allowed before this time.
+    pub not_before: u64,
+}
+
+impl History {
+    pub fn path(cache: &Path, year: u32, day: u32, part: u32) -> PathBuf {
+        cache.join("answers").join(year.to_string()).join(format!("{day:02}-{part}.txt"))
+    }
+
+    pub fn load(path: PathBuf) -> io::Result<Self> {
+        let mut history = History {
+            path,
+            entries: Vec::new(),
+            not_before: 0,
+        };
+
+        let text = match fs::read_to_string(&history.path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(history),
+            Err(e) => return Err(e),
+        };
+
+        for (i, line) in text.lines().enumerate() {
+            let invalid = || {
+                let path = history.path.display();
+
+                io::Error::new(io::ErrorKind::InvalidData, format!("{path}:{}: invalid entry", i + 1))
+            };
+
+            let mut fields = line.splitn(3, ' ');
+            let time: u64 = fields.next().and_then(|t| t.parse().ok()).ok_or_else(invalid)?;
+
+            match (fields.next(), fields.next()) {
+                (Some("wait"), None) => history.not_before = history.not_before.max(time),
+                (Some(judgement), Some(answer)) => history.entries.push(Entry {
+                    time,
+                    answer: answer.to_owned(),
+                    judgement: judgement.parse().map_err(|_| invalid())?,
+                }),
+                _ => return Err(invalid()),
+            }
+        }
+
+        Ok(history)
+    }
+
+    /// The answer judged correct, once the part is solved.
+    pub fn correct(&self) -> Option<&str> {
+        self.entries.iter().find(|e| e.judgement == Judgement::Correct).map(|e| e.answer.as_str())
+    }
+
+    /// Why `answer` must not be submitted at `now`, if the history already tells.
+    pub fn refuse(&self, answer: &str, now: u64) -> Option<String> {
+        if let Some(correct) = self.correct() {
+            return Some(format!("already solved with {correct}"));
+        }
+
+        if let Some(entry) = self.entries.iter().find(|e| e.answer == answer) {
+            return Some(format!("{answer} was already submitted and judged {}", entry.judgement));
+        }
+
+        if let Ok(n) = answer.parse::<i128>() {
+            let bound = |judgement| {
+                self.entries
+                    .iter()
+                    .filter(move |e| e.judgement == judgement)
+                    .filter_map(|e| e.answer.parse::<i128>().ok())
+            };
+
+            if let Some(high) = bound(Judgement::TooHigh).min()
+                && n >= high
+            {
+                return Some(format!("{n} is not below {high}, which is too high"));
+            }
+
+            if let Some(low) = bound(Judgement::TooLow).max()
+                && n <= low
+            {
+                return Some(format!("{n} is not above {low}, which is too low"));
+            }
+        }
+
+        if now < self.not_before {
+            return Some(format!("the site asked to wait another {}s", self.not_before - now));
+        }
+
+        None
+    }
+
+    /// Records the site's response to `answer` submitted at `now`.
+    pub fn record(&mut self, answer: &str, response: &Response, now: u64) -> io::Result<()> {
+        let mut lines = Vec::new();
+
+        if let Verdict::Judged(judgement) = response.verdict {
+            let entry = Entry {
+                time: now,
+                answer: answer.to_owned(),
+                judgement,
+            };
+
+            lines.push(format!("{} {} {}", entry.time, entry.judgement, entry.answer));
+            self.entries.push(entry);
+        }
+
+        if let Some(wait) = response.wait {
+            self.not_before = now + wait.as_secs();
+            lines.push(format!("{} wait", self.not_before));
+        }
+
+        if lines.is_empty() {
+            return Ok(());
+        }
+
+        let mut text = match fs::read_to_string(&self.path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e),
+        };
+
+        for line in lines {
+            text.push_str(&line);
+            text.push('\n');
+        }
+
+        fetch::write_atomically(&self.path, &text)
+    }
+}
+
+pub fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+/// Submits `answer` unless the history refuses it, recording what the site said.
+pub fn submit(
+    client: &Client,
+    history: &mut History,
+    year: u32,
+    day: u32,
+    part: u32,
+    answer: &str,
+) -> io::Result<Response> {
+    fetch::check_day(year, day)?;
+
+    if !(1..=2).contains(&part) {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("no part {part}, parts are 1 and 2")));
+    }
+
+    if answer.is_empty() || answer.contains(char::is_whitespace) {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("'{answer}' does not look like an answer")));
+    }
+
+    if let Some(reason) = history.refuse(answer, now()) {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("not submitting: {reason}")));
+    }
+
+    let html = client.post(&format!("/{year}/day/{day}/answer"), &[("level", &part.to_string()), ("answer", answer)])?;
+    let response = parse_response(&html);
+
+    history.record(answer, &response, now())?;
+
+    Ok(response)
+}
@@ -0,0 +1,170 @@
+//! Re-runs a day whenever anything in its directory changes, be it the sources of its parts or of the crates they
+//! share, its input or its example fixtures, or the shared `aoc` crate does. Files are polled rather than subscribed
+//! to, which is plenty for a handful of small files.
+
+use std::{
+    collections::BTreeMap,
+    fmt::Write as _,
+    fs,
+    io,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::{
+    paths,
+    runner::{self, Example, Run},
+};
+
+/// Time between two looks at the files.
+pub const INTERVAL: Duration = Duration::from_millis(500);
+
+/// Clears the terminal and moves the cursor home, so every run replaces the last.
+const CLEAR: &str = "\x1b[2J\x1b[H";
+
+/// Modification times of the watched files.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Snapshot(BTreeMap<PathBuf, SystemTime>);
+
+impl Snapshot {
+    /// Takes the times of `paths`, walking into directories but leaving out build output and the lock files the runs
+    /// themselves write.
+    pub fn take(paths: &[PathBuf]) -> Self {
+        let mut snapshot = Snapshot::default();
+
+        for path in paths {
+            snapshot.add(path);
+        }
+
+        snapshot
+    }
+
+    fn add(&mut self, path: &Path) {
+        let Ok(metadata) = fs::metadata(path) else {
+            return;
+        };
+
+        if metadata.is_dir() {
+            if path.file_name().is_some_and(|name| name == "target") {
+                return;
+            }
+
+            let Ok(entries) = fs::read_dir(path) else {
+                return;
+            };
+
+            for entry in entries.flatten() {
+                self.add(&entry.path());
+            }
+        } else if path.file_name().is_some_and(|name| name == "Cargo.lock") {
+            // Written by the first build of a part, which would otherwise trigger a second run
+        } else if let Ok(modified) = metadata.modified() {
+            self.0.insert(path.to_path_buf(), modified);
+        }
+    }
+
+    /// Files added, removed or modified since `earlier`.
+    pub fn changes(&self, earlier: &Snapshot) -> Vec<PathBuf> {
+        let mut changed: Vec<PathBuf> = self
+            .0
+            .iter()
+            .filter(|&(path, modified)| earlier.0.get(path) != Some(modified))
+            .map(|(path, _)| path.clone())
+            .collect();
+
+        changed.extend(earlier.0.keys().filter(|path| !self.0.contains_key(*path)).cloned());
+        changed.sort();
+        changed
+    }
+}
+
+/// Everything a day's answers depend on: its directory, holding its parts, the crates they share, its input,
+/// sealed or not, and its example fixtures, and the shared crate.
+pub fn watched(root: &Path, year: u32, day: u32) -> Vec<PathBuf> {
+    vec![paths::day(root, year, day), root.join("aoc/src")]
+}
+
+/// Runs the day on its example, then on its input, describing each part's result.
+pub fn report(root: &Path, year: u32, day: u32) -> String {
+    let day_dir = paths::day(root, year, day);
+    let mut report = String::new();
+    let mut stages = Vec::new();
+
+    if Example::input_path(&day_dir).is_file() {
+        stages.push(("example", true));
+    }
+
+    if runner::has_input(&day_dir) {
+        stages.push(("input", false));
+    }
+
+    if stages.is_empty() {
+        return format!("{year}/{day:02} has neither an example nor an input yet\n");
+    }
+
+    for (stage, example) in stages {
+        match runner::run_day(root, year, day, example) {
+            Ok(runs) => {
+                for (part, run) in runs {
+                    let _ = match run {
+                        Run::Answer(answer) => writeln!(report, "{stage:<8} {part}  {answer}"),
+                        Run::Passed(answer) => writeln!(report, "{stage:<8} {part}  {answer}  pass"),
+                        Run::Failed { expected, actual } => writeln!(
+                            report,
+                            "{stage:<8} {part}  {actual}  FAIL\n{:11}- {expected}\n{:11}+ {actual}",
+                            "", ""
+                        ),
+                    };
+                }
+            }
+            Err(e) => {
+                let _ = writeln!(report, "{stage:<8} error: {e}");
+
+                // Whatever broke the example breaks the input too, and cargo has already said what it was
+                break;
+            }
+        }
+    }
+
+    report
+}
+
+/// Runs the day now and again after every change, until interrupted.
+pub fn watch(root: &Path, year: u32, day: u32) -> io::Result<()> {
+    let watched = watched(root, year, day);
+    let mut snapshot = Snapshot::take(&watched);
+    let mut changed = Vec::new();
+
+    loop {
+        let changed_list: Vec<String> = changed
+            .iter()
+            .map(|path: &PathBuf| path.strip_prefix(root).unwrap_or(path).display().to_string())
+            .collect();
+
+        print!("{CLEAR}{year}/{day:02}");
+
+        if !changed_list.is_empty() {
+            print!(", after changes to {}", changed_list.join(", "));
+        }
+
+        // The header goes out before the runs, so compiler errors end up below it rather than cleared away
+        println!("\n");
+        println!("{}\nwatching for changes, ctrl-c to stop", report(root, year, day));
+
+        loop {
+            thread::sleep(INTERVAL);
+
+            let next = Snapshot::take(&watched);
+
+            changed = next.changes(&snapshot);
+
+            if !changed.is_empty() {
+                // Editors often write a file in several steps, wait for them to finish
+                thread::sleep(INTERVAL);
+                snapshot = Snapshot::take(&watched);
+                break;
+            }
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use std::{
+    io,
+    path::Path,
+};
+
+use aoc::{
+    grid::Grid,
+    input::{self, Ragged},
+    parse::Mode,
+};
+
+fn read_char_grid(path: &Path, mode: Mode) -> io::Result<Grid> {
+    let rows = input::rows(&input::read(path)?, Ragged::Reject, mode)?;
+    let width = rows.first().map_or(0, |row| row.cells.len());
+    let mut grid = Grid::new(width, rows.len(), b'.');
+
+    for (y, row) in rows.into_iter().enumerate() {
+        for (x, c) in row.cells.into_iter().enumerate() {
+            if !c.is_ascii() {
+                mode.reject(row.line, format!("unknown cell '{c}', treating it as empty"))?;
+                continue;
+            }
+
+            grid.set(x, y, c as u8);
+        }
+    }
+
+    Ok(grid)
+}
+
+fn solve(path: &Path, mode: Mode) -> io::Result<u64> {
+    let grid = read_char_grid(path, mode)?;
+
+    Ok(grid.positions(b'#').count() as u64)
+}
+
+fn main() -> io::Result<()> {
+    println!("{}", solve(Path::new("../input"), Mode::from_args())?);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The answer the puzzle gives for the example, `aoc run --example` checking the same against `example.answers`.
+    const EXAMPLE: u64 = 0;
+
+    #[test]
+    fn solves_the_example() -> io::Result<()> {
+        assert_eq!(solve(Path::new("../example"), Mode::Strict)?, EXAMPLE);
+
+        Ok(())
+    }
+}
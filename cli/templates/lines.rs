@@ -0,0 +1,73 @@
+use std::{
+    error,
+    fmt::{self, Display, Formatter},
+    io,
+    num::ParseIntError,
+    path::Path,
+    str::FromStr,
+};
+
+use aoc::{
+    input,
+    parse::{self, Mode},
+};
+
+struct Record {
+    value: u64,
+}
+
+#[derive(Debug)]
+enum ParseRecordError {
+    InvalidNumber(ParseIntError),
+}
+
+impl Display for ParseRecordError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidNumber(e) => write!(f, "Invalid number: {e}"),
+        }
+    }
+}
+
+impl error::Error for ParseRecordError {}
+
+impl FromStr for Record {
+    type Err = ParseRecordError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s.trim().parse().map_err(ParseRecordError::InvalidNumber)?;
+
+        Ok(Record { value })
+    }
+}
+
+fn read_records(path: &Path, mode: Mode) -> io::Result<Vec<Record>> {
+    parse::records(&input::read(path)?, mode)
+}
+
+fn solve(path: &Path, mode: Mode) -> io::Result<u64> {
+    let records = read_records(path, mode)?;
+
+    Ok(records.iter().map(|record| record.value).sum())
+}
+
+fn main() -> io::Result<()> {
+    println!("{}", solve(Path::new("../input"), Mode::from_args())?);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The answer the puzzle gives for the example, `aoc run --example` checking the same against `example.answers`.
+    const EXAMPLE: u64 = 0;
+
+    #[test]
+    fn solves_the_example() -> io::Result<()> {
+        assert_eq!(solve(Path::new("../example"), Mode::Strict)?, EXAMPLE);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+use std::{
+    io,
+    path::Path,
+};
+
+use aoc::{
+    input,
+    parse::Mode,
+};
+
+/// A run of lines between blank lines, each with its line number.
+type Section = Vec<(usize, String)>;
+
+fn read_sections(path: &Path, mode: Mode) -> io::Result<Vec<Section>> {
+    let text = input::read(path)?;
+    let mut sections = vec![Section::new()];
+
+    for (i, line) in text.lines().enumerate() {
+        if !line.is_empty() {
+            sections.last_mut().unwrap().push((i + 1, line.to_owned()));
+        } else if sections.last().is_some_and(|section| section.is_empty()) {
+            mode.reject(i + 1, "blank line")?;
+        } else {
+            sections.push(Section::new());
+        }
+    }
+
+    sections.retain(|section| !section.is_empty());
+
+    Ok(sections)
+}
+
+fn solve(path: &Path, mode: Mode) -> io::Result<u64> {
+    let sections = read_sections(path, mode)?;
+
+    Ok(sections.iter().map(Vec::len).sum::<usize>() as u64)
+}
+
+fn main() -> io::Result<()> {
+    println!("{}", solve(Path::new("../input"), Mode::from_args())?);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The answer the puzzle gives for the example, `aoc run --example` checking the same against `example.answers`.
+    const EXAMPLE: u64 = 0;
+
+    #[test]
+    fn solves_the_example() -> io::Result<()> {
+        assert_eq!(solve(Path::new("../example"), Mode::Strict)?, EXAMPLE);
+
+        Ok(())
+    }
+}
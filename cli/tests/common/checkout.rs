@@ -0,0 +1,47 @@
+//! Scratch checkouts to create days in and run them from.
+
+use std::{
+    env,
+    fs,
+    io,
+    os::unix,
+    path::PathBuf,
+    process,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use aoc_cli::paths;
+
+/// A checkout in a temporary directory, with the shared crate linked in and nothing else, removed when dropped
+/// whether the test passed or not. Its parts build in their own `target` directories, as in the real checkout, since
+/// the parts of a day are all packages named `a` and would mix up their executables in a shared one.
+pub struct Checkout {
+    pub root: PathBuf,
+}
+
+impl Checkout {
+    pub fn new() -> io::Result<Self> {
+        static CHECKOUTS: AtomicUsize = AtomicUsize::new(0);
+
+        let count = CHECKOUTS.fetch_add(1, Ordering::Relaxed);
+        let root = env::temp_dir().join(format!("aoc-test-{}-{count}", process::id()));
+
+        fs::create_dir_all(&root)?;
+
+        let checkout = Checkout { root };
+
+        unix::fs::symlink(paths::root()?.join("aoc"), checkout.root.join("aoc"))?;
+        Ok(checkout)
+    }
+
+    /// Directory of a day's crates.
+    pub fn day(&self, year: u32, day: u32) -> PathBuf {
+        paths::day(&self.root, year, day)
+    }
+}
+
+impl Drop for Checkout {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
@@ -0,0 +1,122 @@
+//! Helpers shared by the tests: a local stand-in for the site, answering plain HTTP on a loopback port so the tests
+//! can exercise the client without a network or a real session, and scratch checkouts, see [`checkout`]. Every test
+//! binary builds all of them and uses some.
+
+#![allow(dead_code)]
+
+pub mod checkout;
+
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+};
+
+#[derive(Clone, Debug)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+pub struct Stub {
+    pub addr: SocketAddr,
+    /// Every request served, in order.
+    pub requests: Receiver<Request>,
+}
+
+impl Stub {
+    /// Serves every request with the status and body `respond` gives for it, one connection at a time.
+    pub fn start<F>(respond: F) -> io::Result<Self>
+    where
+        F: Fn(&Request) -> (u16, String) + Send + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        let (sender, requests) = mpsc::channel();
+
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                // A broken connection only fails the request the client is making
+                let _ = serve(stream, &respond, &sender);
+            }
+        });
+
+        Ok(Stub { addr, requests })
+    }
+
+    pub fn base_url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Requests served since the last call.
+    pub fn served(&self) -> Vec<Request> {
+        self.requests.try_iter().collect()
+    }
+}
+
+fn serve<F>(stream: TcpStream, respond: &F, sender: &Sender<Request>) -> io::Result<()>
+where
+    F: Fn(&Request) -> (u16, String),
+{
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut line = String::new();
+
+    reader.read_line(&mut line)?;
+
+    let mut parts = line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_owned();
+    let path = parts.next().unwrap_or_default().to_owned();
+    let mut headers = Vec::new();
+
+    loop {
+        line.clear();
+        reader.read_line(&mut line)?;
+
+        let header = line.trim_end();
+
+        if header.is_empty() {
+            break;
+        }
+
+        if let Some((key, value)) = header.split_once(':') {
+            headers.push((key.trim().to_owned(), value.trim().to_owned()));
+        }
+    }
+
+    let mut request = Request {
+        method,
+        path,
+        headers,
+        body: String::new(),
+    };
+
+    let length = request.header("Content-Length").and_then(|n| n.parse().ok()).unwrap_or(0);
+    let mut body = vec![0; length];
+
+    reader.read_exact(&mut body)?;
+    request.body = String::from_utf8_lossy(&body).into_owned();
+
+    let (status, body) = respond(&request);
+    let _ = sender.send(request);
+
+    let mut stream = stream;
+
+    write!(
+        stream,
+        "HTTP/1.1 {status} Stub\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()
+}
@@ -0,0 +1,144 @@
+//! The dashboard filled from a scratch checkout, its parts run and drawn off screen.
+
+mod common;
+
+use std::{fs, io};
+
+use aoc_cli::{
+    dashboard::{self, Check, Dashboard, LastRun, Status},
+    paths,
+    runner,
+    scaffold::{self, Template},
+    submit::History,
+};
+use common::checkout::Checkout;
+use ratatui::{Terminal, backend::TestBackend};
+
+/// A part with a witness to check its answer against, which tells on stderr what it was run with.
+const VERIFYING: &str = r#"mod verify;
+
+fn main() {
+    eprintln!("{}", std::env::args().skip(1).collect::<Vec<_>>().join(" "));
+    println!("7");
+}
+"#;
+
+/// A checkout with day 1 solved, part a with what it prints and part b with something else, day 2 registered
+/// without crates, and day 8 whose part b verifies its answer.
+fn checkout() -> io::Result<Checkout> {
+    let checkout = Checkout::new()?;
+    let cache = paths::cache(&checkout.root);
+
+    scaffold::new_day(&checkout.root, 2025, 1, Template::Lines)?;
+    runner::register(&checkout.root, 2025, 2)?;
+    fs::write(checkout.day(2025, 1).join("input"), "1\n2\n")?;
+    scaffold::new_day(&checkout.root, 2025, 8, Template::Lines)?;
+    fs::write(checkout.day(2025, 8).join("input"), "1\n")?;
+    fs::write(checkout.day(2025, 8).join("b/src/main.rs"), VERIFYING)?;
+    fs::write(checkout.day(2025, 8).join("b/src/verify.rs"), "")?;
+
+    for (part, answer) in [(1, "3"), (2, "4")] {
+        let path = History::path(&cache, 2025, 1, part);
+
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(path, format!("0 too-low 1\n0 correct {answer}\n"))?;
+    }
+
+    Ok(checkout)
+}
+
+#[test]
+fn fills_a_row_per_part() -> io::Result<()> {
+    let checkout = checkout()?;
+    let mut rows = dashboard::rows(&checkout.root)?;
+
+    assert!(rows.len() == 6, "every part of every registered day has a row");
+    assert!(rows[0].status == Status::Solved("3".to_owned()), "a part with a correct answer is solved");
+    assert!(rows[2].status == Status::Missing && rows[3].status == Status::Missing, "parts without crates are missing");
+    assert!(rows[4].status == Status::Unsolved, "a part without a correct answer is unsolved");
+    assert!(rows.iter().all(|row| row.check() == Check::Unchecked), "nothing is checked before it runs");
+    assert!(dashboard::run(&checkout.root, &mut rows[2]).is_err(), "a missing part cannot run");
+
+    Ok(())
+}
+
+#[test]
+fn checks_runs_against_the_accepted_answers() -> io::Result<()> {
+    let checkout = checkout()?;
+    let mut rows = dashboard::rows(&checkout.root)?;
+
+    for row in &mut rows[..2] {
+        let runs = dashboard::run(&checkout.root, row)?;
+
+        assert!(runs.timed.answer() == Some("3"), "the part runs on its input");
+    }
+
+    assert!(rows[0].check() == Check::Matches, "an accepted answer matches");
+    assert!(rows[1].check() == Check::Differs, "any other answer differs");
+    assert!(rows[0].cells()[3] == "3" && rows[0].cells()[4] == "ok", "the row shows the answer and the check");
+
+    Ok(())
+}
+
+#[test]
+fn times_a_plain_run_and_traces_another() -> io::Result<()> {
+    let checkout = checkout()?;
+    let mut rows = dashboard::rows(&checkout.root)?;
+
+    assert!(!dashboard::verifies(&checkout.day(2025, 8).join("a")), "parts without a witness do not verify");
+    assert!(dashboard::verifies(&checkout.day(2025, 8).join("b")), "parts with a `verify` module do");
+
+    let runs = dashboard::run(&checkout.root, &mut rows[5])?;
+
+    assert!(runs.timed.stderr.trim().is_empty(), "the timed run is given no arguments");
+    assert!(runs.checked.stderr.trim() == "--trace debug --verify", "the other traces and verifies");
+    assert!(rows[5].cells()[5] == "yes", "the row shows that the answer was verified");
+
+    dashboard::run(&checkout.root, &mut rows[4])?;
+
+    assert!(rows[4].last.as_ref().is_some_and(|last| !last.verified), "other parts are not verified");
+
+    Ok(())
+}
+
+#[test]
+fn remembers_runs_between_sessions() -> io::Result<()> {
+    let checkout = checkout()?;
+    let mut rows = dashboard::rows(&checkout.root)?;
+
+    dashboard::run(&checkout.root, &mut rows[0])?;
+
+    assert!(dashboard::rows(&checkout.root)? == rows, "runs are reloaded");
+
+    let path = LastRun::path(&paths::cache(&checkout.root), 2025, 2, "a");
+    let failed = LastRun {
+        time: 1,
+        elapsed: Default::default(),
+        answer: None,
+        verified: false,
+    };
+
+    failed.save(&path)?;
+    assert!(LastRun::load(&path)? == Some(failed), "failed runs are remembered");
+
+    Ok(())
+}
+
+#[test]
+fn draws_the_rows_and_the_selected_run() -> io::Result<()> {
+    let checkout = checkout()?;
+    let mut dashboard = Dashboard::new(&checkout.root)?;
+    let mut terminal = Terminal::new(TestBackend::new(100, 30))?;
+
+    dashboard.select(1);
+    dashboard.run_selected();
+    terminal.draw(|frame| dashboard.draw(frame))?;
+
+    let screen: String = terminal.backend().buffer().content().iter().map(|cell| cell.symbol()).collect();
+
+    for text in ["2025/01", "solved", "WRONG", "missing", " output ", " trace "] {
+        assert!(screen.contains(text), "the dashboard shows {text:?}");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+//! Examples extracted from a saved puzzle page and written as fixtures.
+
+use std::{env, fs, io, process};
+
+use aoc_cli::{
+    examples::{self, Fixtures},
+    runner::Example,
+};
+
+/// A page saved after solving part one: a sample of the rules, the example, a worked step and the answers.
+const PAGE: &str = r#"<!DOCTYPE html>
+<html lang="en-us"><head><title>Day 7 - Advent of Code 2025</title></head><body>
+<main>
+<article class="day-desc"><h2>--- Day 7: Laboratories ---</h2>
+<p>Beams are drawn like this:</p>
+<pre><code>|
+^</code></pre>
+<p>For example:</p>
+<pre><code>..S..
+.....
+..^..
+.&lt;.&gt;.
+</code></pre>
+<p>After one step, the beam looks like <code>|</code>:</p>
+<pre><code>..S..
+..|..
+</code></pre>
+<p>In this example, the beam is split a total of <code><em>21</em></code> times.</p>
+</article>
+<p>Your puzzle answer was <code>1555</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>Consider the same example:</p>
+<pre><code>..S..
+..|..
+</code></pre>
+<p>In total, the particle ends up on <em><code>40</code></em> different timelines.</p>
+</article>
+</main></body></html>
+"#;
+
+const EXAMPLE: &str = "..S..\n.....\n..^..\n.<.>.\n";
+const STEP: &str = "..S..\n..|..\n";
+
+#[test]
+fn finds_the_blocks_and_answers_of_both_parts() {
+    let parts = examples::parse(PAGE);
+
+    assert_eq!(parts.len(), 2, "both parts are found");
+    assert!(parts[0].blocks.len() == 3 && parts[0].blocks[1] == EXAMPLE, "blocks are unescaped");
+    assert_eq!(parts[0].answer.as_deref(), Some("21"), "part one's answer is the last emphasised code");
+    assert_eq!(parts[1].answer.as_deref(), Some("40"), "emphasis around code counts as well");
+}
+
+#[test]
+fn extracts_the_picked_blocks() -> io::Result<()> {
+    assert!(examples::extract(PAGE, [None, None]).is_err(), "several blocks need a choice");
+    assert!(examples::extract(PAGE, [Some(4), Some(0)]).is_err(), "a missing block cannot be picked");
+
+    let same = examples::extract(PAGE, [Some(2), Some(0)])?;
+
+    assert_eq!(same.inputs, [Some(EXAMPLE.to_owned()), None], "part two can reuse part one's example");
+    assert_eq!(same.answers, [Some("21".to_owned()), Some("40".to_owned())], "both answers are taken");
+
+    let own = examples::extract(PAGE, [Some(2), Some(1)])?;
+
+    assert_eq!(own.inputs[1].as_deref(), Some(STEP), "part two can have an example of its own");
+
+    Ok(())
+}
+
+#[test]
+fn writes_fixtures_keeping_known_answers() -> io::Result<()> {
+    let day_dir = env::temp_dir().join(format!("aoc-test-examples-{}", process::id()));
+    let same = examples::extract(PAGE, [Some(2), Some(0)])?;
+    let own = examples::extract(PAGE, [Some(2), Some(1)])?;
+
+    examples::write(&day_dir, &own)?;
+
+    let example = Example::load(&day_dir)?.expect("an example is written");
+
+    assert_eq!(fs::read_to_string(&example.inputs[1])?, STEP, "part two reads its own example");
+
+    let first_only = Fixtures {
+        parts: 1,
+        inputs: [Some(EXAMPLE.to_owned()), None],
+        answers: [Some("21".to_owned()), None],
+    };
+
+    examples::write(&day_dir, &first_only)?;
+
+    let example = Example::load(&day_dir)?.expect("an example is written");
+
+    assert_eq!(example.answers[1].as_deref(), Some("40"), "known answers are kept");
+    assert!(Example::second_input_path(&day_dir).exists(), "a page without part two keeps its example");
+
+    examples::write(&day_dir, &same)?;
+
+    assert!(!Example::second_input_path(&day_dir).exists(), "a reused example replaces part two's own");
+
+    fs::remove_dir_all(&day_dir)
+}
@@ -0,0 +1,112 @@
+//! The input downloader against a local stub of the site.
+
+mod common;
+
+use std::{
+    env,
+    fs,
+    io,
+    path::PathBuf,
+    process,
+    time::{Duration, Instant},
+};
+
+use aoc_cli::{config::Config, fetch::Client};
+use common::Stub;
+
+const SESSION: &str = "0123abcd";
+const INPUT: &str = "L68\nR48\n";
+const INTERVAL: Duration = Duration::from_millis(300);
+
+fn site() -> io::Result<Stub> {
+    Stub::start(|request| {
+        if request.header("Cookie") != Some(&format!("session={SESSION}")) {
+            return (400, "Puzzle inputs differ by user.  Please log in to get your puzzle input.".to_owned());
+        }
+
+        match request.path.as_str() {
+            "/2025/day/1/input" => (200, INPUT.to_owned()),
+            "/2025/day/2/input" => (500, "Internal Server Error".to_owned()),
+            _ => (404, "Not Found".to_owned()),
+        }
+    })
+}
+
+fn config(stub: &Stub) -> Config {
+    Config {
+        session: Some(SESSION.to_owned()),
+        contact: Some("someone@example.com".to_owned()),
+        base_url: stub.base_url(),
+    }
+}
+
+fn cache(name: &str) -> PathBuf {
+    env::temp_dir().join(format!("aoc-test-fetch-{name}-{}", process::id()))
+}
+
+#[test]
+fn downloads_once_then_reads_the_cache() -> io::Result<()> {
+    let stub = site()?;
+    let cache = cache("cached");
+    let client = Client::new(config(&stub), cache.clone()).with_interval(INTERVAL);
+
+    let fetched = client.input(2025, 1, false)?;
+    let served = stub.served();
+
+    assert!(fetched.text == INPUT && !fetched.cached, "first fetch downloads the input");
+    assert_eq!(served.len(), 1, "first fetch makes one request");
+    assert_eq!(served[0].method, "GET");
+    assert!(
+        served[0].header("User-Agent").is_some_and(|agent| agent.contains("someone@example.com")),
+        "the User-Agent names the contact"
+    );
+
+    let fetched = client.input(2025, 1, false)?;
+
+    assert!(fetched.text == INPUT && fetched.cached, "second fetch reads the cache");
+    assert!(stub.served().is_empty(), "second fetch makes no request");
+
+    let start = Instant::now();
+    let refreshed = client.input(2025, 1, true)?;
+
+    assert!(!refreshed.cached && stub.served().len() == 1, "refresh downloads again");
+    assert!(start.elapsed() >= INTERVAL / 2, "requests in quick succession are throttled");
+
+    fs::remove_dir_all(&cache)
+}
+
+#[test]
+fn reports_failures_without_caching_them() -> io::Result<()> {
+    let stub = site()?;
+    let cache = cache("failures");
+    let config = config(&stub);
+    let client = Client::new(config.clone(), cache.clone()).with_interval(Duration::ZERO);
+
+    let error = client.input(2025, 5, false).err().map(|e| e.kind());
+
+    assert_eq!(error, Some(io::ErrorKind::NotFound), "a locked day is reported as not available");
+    assert!(client.input(2025, 2, false).is_err(), "a server error fails the fetch");
+    assert!(!cache.join("inputs/2025/05.txt").exists(), "failed fetches are not cached");
+
+    let stranger = Client::new(
+        Config {
+            session: Some("expired".to_owned()),
+            ..config.clone()
+        },
+        cache.clone(),
+    )
+    .with_interval(Duration::ZERO);
+
+    let error = stranger.input(2025, 3, false).err().map(|e| e.kind());
+
+    assert_eq!(error, Some(io::ErrorKind::PermissionDenied), "a rejected session is reported");
+
+    let anonymous = Client::new(Config { session: None, ..config }, cache.clone());
+
+    assert!(anonymous.input(2025, 4, false).is_err(), "fetching without a session fails");
+    assert_eq!(stub.served().len(), 3, "nothing is requested without a session");
+    assert!(client.input(2025, 13, false).is_err(), "days past 12 are refused in 2025");
+    assert!(client.input(2024, 25, false).is_err_and(|e| e.kind() == io::ErrorKind::NotFound), "2024 has 25 days");
+
+    fs::remove_dir_all(&cache)
+}
@@ -0,0 +1,117 @@
+//! Inputs generated for every day, checked for what their parsers take for granted.
+
+use std::{
+    collections::{HashMap, HashSet},
+    io,
+};
+
+use aoc::rng::Rng;
+use aoc_cli::generator::{self, DAYS};
+
+fn generate(day: u32, size: usize, seed: u64) -> io::Result<String> {
+    generator::generate(day, size, &mut Rng::new(seed))
+}
+
+/// Corners in order around the loop, each joined to the next by a horizontal or vertical edge.
+fn is_loop(text: &str) -> bool {
+    let corners: Vec<(u64, u64)> = text
+        .lines()
+        .filter_map(|line| line.split_once(','))
+        .filter_map(|(x, y)| Some((x.parse().ok()?, y.parse().ok()?)))
+        .collect();
+
+    let unique: HashSet<_> = corners.iter().collect();
+
+    unique.len() == corners.len()
+        && (0..corners.len()).all(|i| {
+            let (a, b, c) = (corners[i], corners[(i + 1) % corners.len()], corners[(i + 2) % corners.len()]);
+
+            (a.0 == b.0) != (a.1 == b.1) && (a.0 == b.0) != (b.0 == c.0)
+        })
+}
+
+/// Every button lights one of the machine's lights, and there is a joltage per light.
+fn are_machines(text: &str) -> bool {
+    text.lines().all(|line| {
+        let fields: Vec<&str> = line.split(' ').collect();
+        let lights = fields[0].len() - 2;
+        let joltages = fields[fields.len() - 1].split(',').count();
+
+        joltages == lights
+            && fields[1..fields.len() - 1].iter().all(|button| {
+                button
+                    .trim_matches(['(', ')'])
+                    .split(',')
+                    .all(|light| light.parse::<usize>().is_ok_and(|light| light < lights))
+            })
+    })
+}
+
+/// Servers reached from `from`, `from` included.
+fn reached<'a>(network: &HashMap<&'a str, Vec<&'a str>>, from: &'a str) -> HashSet<&'a str> {
+    let mut seen = HashSet::from([from]);
+    let mut stack = vec![from];
+
+    while let Some(server) = stack.pop() {
+        for &next in network.get(server).into_iter().flatten() {
+            if seen.insert(next) {
+                stack.push(next);
+            }
+        }
+    }
+
+    seen
+}
+
+fn is_network(text: &str) -> bool {
+    let network: HashMap<&str, Vec<&str>> = text
+        .lines()
+        .filter_map(|line| line.split_once(": "))
+        .map(|(name, connected)| (name, connected.split(' ').collect()))
+        .collect();
+
+    let from_svr = reached(&network, "svr");
+
+    network.len() == text.lines().count()
+        && from_svr.len() == network.len() + 1
+        && network.keys().all(|&server| reached(&network, server).contains("out"))
+        && reached(&network, "fft").contains("dac")
+        && from_svr.contains("you")
+}
+
+#[test]
+fn seeds_give_whole_reproducible_inputs() -> io::Result<()> {
+    for day in DAYS {
+        let size = generator::default_size(day)?;
+        let text = generate(day, size, 1)?;
+
+        assert!(text == generate(day, size, 1)?, "day {day}: the same seed gives the same input");
+        assert!(text != generate(day, size, 2)?, "day {day}: another seed gives another input");
+        assert!(text.ends_with('\n') && !text.contains("\n\n\n"), "day {day}: inputs are whole lines");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn sizes_count_what_each_day_has_many_of() -> io::Result<()> {
+    assert_eq!(generate(1, 25, 3)?.lines().count(), 25, "sizes count rotations");
+    assert_eq!(generate(2, 25, 3)?.split(',').count(), 25, "sizes count ID ranges");
+    assert!(generate(4, 25, 3)?.lines().all(|row| row.len() == 25), "rolls fill a square");
+    assert_eq!(generate(6, 25, 3)?.lines().last().unwrap().matches(['+', '*']).count(), 25, "sizes count problems");
+    assert!(generate(8, 25, 3).is_err(), "too few junction boxes are refused");
+    assert!(generate(13, 1, 3).is_err(), "days without a generator are refused");
+
+    Ok(())
+}
+
+#[test]
+fn inputs_are_well_formed() -> io::Result<()> {
+    for seed in 0..50 {
+        assert!(is_loop(&generate(9, 1 + seed as usize % 20, seed)?), "tiles make a rectilinear loop");
+        assert!(are_machines(&generate(10, 5, seed)?), "machines are well formed");
+        assert!(is_network(&generate(11, 4 + seed as usize, seed)?), "every server is on a path from svr to out");
+    }
+
+    Ok(())
+}
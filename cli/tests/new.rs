@@ -0,0 +1,127 @@
+//! Days generated from every template into a scratch checkout, run on their examples.
+
+mod common;
+
+use std::{
+    fs,
+    io,
+    path::Path,
+    process::Command,
+};
+
+use aoc_cli::{
+    runner::{self, Example, Run},
+    scaffold::{self, Template},
+};
+use common::checkout::Checkout;
+
+/// A day per template, with an example and what the template's solver prints for it.
+const DAYS: [(u32, Template, &str, &str); 3] = [
+    (1, Template::Lines, "1\n2\n3\n", "6"),
+    (2, Template::Grid, "#..\n.#.\n..#\n", "3"),
+    (3, Template::Sections, "0:\n##\n\n4x4: 1\n", "3"),
+];
+
+/// Whether the tests of a generated part pass, their output kept out of this test's.
+fn cargo_test(part_dir: &Path) -> io::Result<bool> {
+    let output = Command::new("cargo").args(["test", "--quiet"]).current_dir(part_dir).output()?;
+
+    Ok(output.status.success())
+}
+
+#[test]
+fn creates_both_parts_and_an_example_stub() -> io::Result<()> {
+    let checkout = Checkout::new()?;
+    let created = scaffold::new_day(&checkout.root, 2025, 1, Template::Lines)?;
+    let day_dir = checkout.day(2025, 1);
+
+    for part in ["a", "b"] {
+        for file in ["Cargo.toml", "rustfmt.toml", "src/main.rs"] {
+            assert!(created.contains(&day_dir.join(part).join(file)), "{part}/{file} is created");
+        }
+    }
+
+    assert!(day_dir.join("example").is_file(), "an empty example is created");
+    assert!(day_dir.join("example.answers").is_file(), "an example stub is created");
+
+    Ok(())
+}
+
+#[test]
+fn generated_tests_pass_on_the_empty_example() -> io::Result<()> {
+    let checkout = Checkout::new()?;
+
+    for (day, template, _, _) in DAYS {
+        scaffold::new_day(&checkout.root, 2025, day, template)?;
+
+        assert!(cargo_test(&checkout.day(2025, day).join("a"))?, "the {template:?} test stub passes");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn generated_days_solve_their_examples() -> io::Result<()> {
+    let checkout = Checkout::new()?;
+
+    for (day, template, example, answer) in DAYS {
+        scaffold::new_day(&checkout.root, 2025, day, template)?;
+
+        let day_dir = checkout.day(2025, day);
+
+        fs::write(day_dir.join("example"), example)?;
+        Example::write_answers(&day_dir, &[Some(answer.to_owned()), Some(answer.to_owned())])?;
+
+        let runs = runner::run_day(&checkout.root, 2025, day, true)?;
+
+        assert_eq!(runs.len(), 2, "both parts run");
+
+        for (part, run) in runs {
+            if let Run::Failed { expected, actual } = run {
+                panic!("{template:?} part {part} printed {actual}, expected {expected}");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn never_overwrites_an_existing_day() -> io::Result<()> {
+    let checkout = Checkout::new()?;
+    let main = checkout.day(2025, 1).join("a/src/main.rs");
+
+    scaffold::new_day(&checkout.root, 2025, 1, Template::Lines)?;
+    fs::write(&main, "fn main() {}\n")?;
+
+    let error = scaffold::new_day(&checkout.root, 2025, 1, Template::Grid).err().map(|e| e.kind());
+
+    assert_eq!(error, Some(io::ErrorKind::AlreadyExists), "an existing day is refused");
+    assert_eq!(fs::read_to_string(&main)?, "fn main() {}\n", "its sources are kept");
+
+    Ok(())
+}
+
+#[test]
+fn registers_new_days_and_ignores_their_inputs() -> io::Result<()> {
+    let checkout = Checkout::new()?;
+
+    for day in [3, 1] {
+        scaffold::new_day(&checkout.root, 2025, day, Template::Lines)?;
+    }
+
+    assert_eq!(runner::registered(&checkout.root)?, [(2025, 1), (2025, 3)], "new days are registered in order");
+    assert!(checkout.root.join("2025/.gitignore").is_file(), "inputs of a new year are ignored");
+
+    Ok(())
+}
+
+#[test]
+fn refuses_days_past_the_last_of_the_year() -> io::Result<()> {
+    let checkout = Checkout::new()?;
+
+    assert!(scaffold::new_day(&checkout.root, 2025, 13, Template::Lines).is_err(), "days past 12 are refused in 2025");
+    assert!(!checkout.day(2025, 13).exists(), "nothing is created for them");
+
+    Ok(())
+}
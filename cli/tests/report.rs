@@ -0,0 +1,109 @@
+//! The report on a scratch checkout with a solved, a failing and a missing part.
+
+mod common;
+
+use std::{fs, io, time::Duration};
+
+use aoc_cli::{
+    paths,
+    report::{self, Entry, Options, Outcome},
+    runner,
+    scaffold::{self, Template},
+    submit::History,
+};
+use common::checkout::Checkout;
+
+/// A checkout with day 1, whose part a is solved and part b panics, and day 2 registered without crates.
+fn checkout() -> io::Result<Checkout> {
+    let checkout = Checkout::new()?;
+
+    scaffold::new_day(&checkout.root, 2025, 1, Template::Lines)?;
+    runner::register(&checkout.root, 2025, 2)?;
+
+    let day_dir = checkout.day(2025, 1);
+    let panics = "fn main() {\n    panic!(\"<no answer>\");\n}\n";
+
+    fs::write(day_dir.join("input"), "1\n2\n")?;
+    fs::write(day_dir.join("b/src/main.rs"), panics)?;
+
+    let history = History::path(&paths::cache(&checkout.root), 2025, 1, 1);
+
+    fs::create_dir_all(history.parent().unwrap())?;
+    fs::write(history, "0 correct 3\n")?;
+
+    Ok(checkout)
+}
+
+/// What [`checkout`] reports, without running it.
+fn entries() -> Vec<Entry> {
+    let entry = |day, part, outcome, accepted| Entry {
+        day,
+        part,
+        outcome,
+        accepted,
+    };
+
+    vec![
+        entry(
+            1,
+            "a",
+            Outcome::Ran {
+                answer: "3".to_owned(),
+                elapsed: Duration::from_millis(2),
+                memory: 1 << 20,
+            },
+            Some(true),
+        ),
+        entry(1, "b", Outcome::Failed("thread 'main' panicked: <no answer>".to_owned()), None),
+        entry(2, "a", Outcome::Missing, None),
+        entry(2, "b", Outcome::Missing, None),
+    ]
+}
+
+fn options(redact: bool) -> Options {
+    Options {
+        redact,
+        source_url: "https://example.com/tree/".to_owned(),
+    }
+}
+
+#[test]
+fn runs_every_part_on_its_input() -> io::Result<()> {
+    let checkout = checkout()?;
+    let mut reported = 0;
+    let entries = report::collect(&checkout.root, 2025, |_| reported += 1)?;
+
+    assert!(entries.len() == 4 && reported == 4, "every part of every registered day is reported");
+
+    let Outcome::Ran { answer, memory, .. } = &entries[0].outcome else {
+        panic!("part a did not run: {:?}", entries[0].outcome);
+    };
+
+    assert!(answer == "3", "the part runs on its input");
+    assert!(*memory > 0, "the part's peak memory is measured");
+    assert!(entries[0].accepted == Some(true), "an accepted answer is marked");
+    assert!(matches!(&entries[1].outcome, Outcome::Failed(_)), "a panicking part fails");
+    assert!(entries[2].outcome == Outcome::Missing, "a part without a crate is missing");
+
+    Ok(())
+}
+
+#[test]
+fn renders_answers_sources_and_charts() {
+    let html = report::render(2025, &entries(), &options(false));
+
+    assert!(html.contains("<code>3</code>"), "answers are shown");
+    assert!(html.contains("href=\"https://example.com/tree/2025/01/a/src/main.rs\""), "sources are linked");
+    assert!(!html.contains("2025/02/a/src/main.rs"), "missing parts are not linked");
+    assert!(html.contains("&lt;no answer&gt;") && !html.contains("<no answer>"), "failures are escaped");
+    assert!(html.matches("<svg").count() == 2 && html.contains("<rect"), "the charts are drawn");
+    assert!(html.contains("1 of 4 parts accepted"), "the summary counts accepted parts");
+}
+
+#[test]
+fn redacts_answers_and_failures() {
+    let html = report::render(2025, &entries(), &options(true));
+
+    assert!(!html.contains("<code>3</code>") && html.contains("redacted"), "answers can be redacted");
+    assert!(!html.contains("no answer") && html.contains(">failed<"), "the output of failures is redacted too");
+}
@@ -0,0 +1,188 @@
+//! A scratch day sealed, run from its sealed files alone, rekeyed and its plaintext staged.
+
+mod common;
+
+use std::{
+    fs,
+    io,
+    path::Path,
+    process::Command,
+};
+
+use aoc::vault::{self, Key};
+use aoc_cli::{
+    paths,
+    scaffold::{self, Template},
+    seal,
+    submit::History,
+};
+use common::checkout::Checkout;
+
+fn git(root: &Path, args: &[&str]) -> io::Result<()> {
+    let status = Command::new("git").args(args).current_dir(root).status()?;
+
+    assert!(status.success(), "git {} succeeds", args.join(" "));
+
+    Ok(())
+}
+
+/// What the `aoc` command prints when run in `root` with `key` as the input key.
+fn aoc(root: &Path, key: &Key, args: &[&str]) -> io::Result<String> {
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(args)
+        .current_dir(root)
+        .env("AOC_INPUT_KEY", key.to_hex())
+        .output()?;
+
+    assert!(output.status.success(), "aoc {}: {}", args.join(" "), String::from_utf8_lossy(&output.stderr));
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// A checkout with day 1, its input and part a's accepted answer sealed under `key`, and their plaintext removed, as
+/// CI has it.
+fn sealed(key: &Key) -> io::Result<Checkout> {
+    let checkout = Checkout::new()?;
+    let day_dir = checkout.day(2025, 1);
+    let history = History::path(&paths::cache(&checkout.root), 2025, 1, 1);
+
+    scaffold::new_day(&checkout.root, 2025, 1, Template::Lines)?;
+    fs::write(day_dir.join("input"), "1\n2\n")?;
+    fs::create_dir_all(history.parent().unwrap())?;
+    fs::write(&history, "0 correct 3\n")?;
+    seal::seal_day(&checkout.root, key, 2025, 1)?;
+    fs::remove_file(day_dir.join("input"))?;
+    fs::remove_file(&history)?;
+
+    Ok(checkout)
+}
+
+#[test]
+fn seals_text_that_only_its_key_opens() {
+    let key = Key::generate();
+    let other = Key::generate();
+    let sealed = vault::seal(&key, b"1\n2\n");
+
+    assert!(vault::open(&key, &sealed).ok().as_deref() == Some(&b"1\n2\n"[..]), "sealed text opens again");
+    assert!(vault::open(&other, &sealed).is_err(), "another key cannot open it");
+    assert!(vault::seal(&key, b"1\n2\n") != sealed, "every sealing uses a fresh nonce");
+    assert!(Key::from_hex(&key.to_hex()).ok() == Some(key.clone()), "keys are written as hex");
+
+    let mut altered = sealed.clone();
+    let last = altered.len() - 1;
+
+    altered[last] ^= 1;
+
+    assert!(vault::open(&key, &altered).is_err(), "an altered file does not open");
+}
+
+#[test]
+fn seals_the_input_and_accepted_answers() -> io::Result<()> {
+    let key = Key::generate();
+    let checkout = Checkout::new()?;
+    let day_dir = checkout.day(2025, 1);
+    let history = History::path(&paths::cache(&checkout.root), 2025, 1, 1);
+
+    scaffold::new_day(&checkout.root, 2025, 1, Template::Lines)?;
+    fs::write(day_dir.join("input"), "1\n2\n")?;
+    fs::create_dir_all(history.parent().unwrap())?;
+    fs::write(&history, "0 correct 3\n")?;
+
+    let changed = seal::seal_day(&checkout.root, &key, 2025, 1)?;
+
+    assert!(changed == [day_dir.join("input.enc"), day_dir.join("answers.enc")], "the input and answers are sealed");
+    assert!(seal::seal_day(&checkout.root, &key, 2025, 1)?.is_empty(), "unchanged files stay");
+    assert!(
+        vault::open_file(&key, &day_dir.join("answers")).is_ok_and(|text| text.contains("a = 3\nb = ?\n")),
+        "the accepted answers are sealed, unknown ones marked"
+    );
+
+    fs::remove_file(&history)?;
+
+    assert!(seal::seal_day(&checkout.root, &key, 2025, 1)?.is_empty(), "answers sealed before stay when sealing again");
+
+    Ok(())
+}
+
+#[test]
+fn runs_parts_from_the_sealed_files_alone() -> io::Result<()> {
+    let key = Key::generate();
+    let checkout = sealed(&key)?;
+    let ran = aoc(&checkout.root, &key, &["run", "2025", "1"])?;
+
+    assert!(ran.contains("2025/01 a: 3 ok"), "parts read the sealed input and are checked against its answers");
+    assert!(ran.contains("2025/01 b: 3\n"), "parts without an answer are not checked");
+
+    let out = checkout.root.join("report");
+    let out_arg = out.to_string_lossy();
+
+    aoc(&checkout.root, &key, &["report", "2025", "--out", &out_arg])?;
+
+    let html = fs::read_to_string(out.join("2025.html"))?;
+
+    assert!(html.contains("1 of 2 parts accepted, 2 of 2 parts ran"), "the report takes the sealed input and answers");
+
+    Ok(())
+}
+
+#[test]
+fn rekeys_only_with_the_current_key() -> io::Result<()> {
+    let key = Key::generate();
+    let other = Key::generate();
+    let checkout = sealed(&key)?;
+    let input = checkout.day(2025, 1).join("input");
+
+    assert!(seal::rekey(&checkout.root, &other, &key).is_err(), "rekeying needs the current key");
+    assert!(
+        vault::open_file(&key, &input).is_ok_and(|text| text == "1\n2\n"),
+        "a failed rekey leaves the files as they were"
+    );
+
+    let rekeyed = seal::rekey(&checkout.root, &key, &other)?;
+
+    assert!(rekeyed.len() == 2, "rekeying seals every file again");
+    assert!(vault::open_file(&other, &input).is_ok_and(|text| text == "1\n2\n"), "the new key opens the input");
+    assert!(vault::open_file(&key, &input).is_err(), "the old key does not");
+
+    Ok(())
+}
+
+#[test]
+fn refuses_staged_plaintext() -> io::Result<()> {
+    let key = Key::generate();
+    let checkout = sealed(&key)?;
+    let root = &checkout.root;
+
+    git(root, &["init", "--quiet"])?;
+    fs::write(checkout.day(2025, 1).join("input"), "1\n2\n")?;
+    fs::write(root.join("copy.txt"), "1\n2\n")?;
+    git(root, &["add", "2025/01/input.enc", "2025/01/a"])?;
+
+    assert!(seal::staged_plaintext(root)?.is_empty(), "sealed files may be committed");
+
+    fs::write(checkout.day(2025, 1).join("input.partial"), "1\n")?;
+    git(root, &["add", "--force", "2025/01/input", "2025/01/input.partial", "copy.txt"])?;
+
+    let flagged = seal::staged_plaintext(root)?;
+
+    assert!(
+        flagged == ["2025/01/input", "2025/01/input.partial", "copy.txt"],
+        "plaintext inputs and unfinished downloads are refused, under any name"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn installs_the_check_as_the_pre_commit_hook() -> io::Result<()> {
+    let checkout = Checkout::new()?;
+
+    git(&checkout.root, &["init", "--quiet"])?;
+
+    let hook = seal::install_hook(&checkout.root)?;
+
+    assert!(hook == checkout.root.join(".git/hooks/pre-commit"), "the check is installed as the pre-commit hook");
+    assert!(seal::install_hook(&checkout.root).is_ok(), "installing again is harmless");
+
+    Ok(())
+}
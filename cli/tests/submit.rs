@@ -0,0 +1,133 @@
+//! The answer submission client against a local stub of the site.
+
+mod common;
+
+use std::{env, fs, io, process, time::Duration};
+
+use aoc_cli::{
+    config::Config,
+    fetch::Client,
+    submit::{self, History, Judgement, Verdict},
+};
+use common::Stub;
+
+const ANSWER: i64 = 4277556;
+
+const RIGHT: &str = "That's the right answer!  You are <em>one gold star</em> closer.";
+const TOO_HIGH: &str = "That's not the right answer; your answer is too high.  If you're stuck, make sure you're using \
+    the full input data; there are also some general tips on the <a href=\"/2025/about\">about page</a>.  Please wait \
+    one minute before trying again.";
+const TOO_LOW: &str = "That's not the right answer; your answer is too low.  Please wait one minute before trying \
+    again.";
+const WRONG: &str = "That's not the right answer.  Please wait 5 minutes before trying again.";
+const TOO_SOON: &str = "You gave an answer too recently; you have to wait after submitting an answer before trying \
+    again.  You have 1m 5s left to wait. <a href=\"/2025/day/6\">[Return to Day 6]</a>";
+const SOLVED: &str = "You don't seem to be solving the right level.  Did you already complete it?";
+
+fn page(text: &str) -> String {
+    format!("<!DOCTYPE html>\n<html><body><main>\n<article><p>{text}</p></article>\n</main></body></html>")
+}
+
+/// Answers like the site: judges the `answer` field of the form, or says to wait when it is `soon`.
+fn respond(body: &str) -> (u16, String) {
+    let answer = body
+        .split('&')
+        .find_map(|field| field.strip_prefix("answer="))
+        .unwrap_or_default();
+
+    if answer == "soon" {
+        return (200, page(TOO_SOON));
+    }
+
+    let text = match answer.parse::<i64>() {
+        Ok(ANSWER) => RIGHT,
+        Ok(n) if n > ANSWER => TOO_HIGH,
+        Ok(_) => TOO_LOW,
+        Err(_) => WRONG,
+    };
+
+    (200, page(text))
+}
+
+#[test]
+fn reads_verdicts_and_waits_from_pages() {
+    let responses = [
+        (RIGHT, Verdict::Judged(Judgement::Correct), None),
+        (TOO_HIGH, Verdict::Judged(Judgement::TooHigh), Some(60)),
+        (WRONG, Verdict::Judged(Judgement::Wrong), Some(300)),
+        (TOO_SOON, Verdict::TooSoon, Some(65)),
+        (SOLVED, Verdict::Solved, None),
+    ];
+
+    for (text, verdict, wait) in responses {
+        let response = submit::parse_response(&page(text));
+
+        assert_eq!(response.verdict, verdict, "verdict of '{text}'");
+        assert_eq!(response.wait, wait.map(Duration::from_secs), "wait of '{text}'");
+    }
+}
+
+#[test]
+fn submits_within_known_bounds() -> io::Result<()> {
+    let stub = Stub::start(|request| {
+        if request.method != "POST" || request.path != "/2025/day/6/answer" {
+            return (404, "Not Found".to_owned());
+        }
+
+        respond(&request.body)
+    })?;
+
+    let cache = env::temp_dir().join(format!("aoc-test-submit-{}", process::id()));
+    let config = Config {
+        session: Some("0123abcd".to_owned()),
+        contact: None,
+        base_url: stub.base_url(),
+    };
+
+    let client = Client::new(config, cache.clone()).with_interval(Duration::ZERO);
+    let path = History::path(&cache, 2025, 6, 1);
+    let mut history = History::load(path.clone())?;
+
+    let submit = |history: &mut History, answer: &str| submit::submit(&client, history, 2025, 6, 1, answer);
+
+    let response = submit(&mut history, "5000000")?;
+    let served = stub.served();
+
+    assert_eq!(response.verdict, Verdict::Judged(Judgement::TooHigh), "a high answer is judged too high");
+    assert!(served.len() == 1 && served[0].body.contains("level=1"), "the part is posted as the level");
+    assert!(submit(&mut history, "4000000").is_err(), "the cooldown is respected");
+    assert!(stub.served().is_empty(), "nothing is posted during the cooldown");
+
+    history.not_before = 0;
+
+    assert!(submit(&mut history, "5000000").is_err(), "a rejected answer is not sent again");
+    assert!(submit(&mut history, "6000000").is_err(), "an answer above a too high one is refused");
+
+    let response = submit(&mut history, "4000000")?;
+
+    assert_eq!(response.verdict, Verdict::Judged(Judgement::TooLow), "a low answer is judged too low");
+
+    history.not_before = 0;
+
+    assert!(submit(&mut history, "3999999").is_err(), "an answer below a too low one is refused");
+    assert_eq!(stub.served().len(), 1, "refused answers are never posted");
+
+    let response = submit(&mut history, "soon")?;
+
+    assert_eq!(response.verdict, Verdict::TooSoon, "an early answer is reported");
+    assert_eq!(response.wait, Some(Duration::from_secs(65)), "the wait is read from the page");
+    assert!(!history.entries.iter().any(|e| e.answer == "soon"), "an unjudged answer is not recorded");
+
+    history.not_before = 0;
+
+    let response = submit(&mut history, &ANSWER.to_string())?;
+
+    assert_eq!(response.verdict, Verdict::Judged(Judgement::Correct), "the right answer is accepted");
+    assert!(submit(&mut history, "4277557").is_err(), "nothing is sent once the part is solved");
+
+    let reloaded = History::load(path)?;
+
+    assert!(reloaded.entries == history.entries, "the history survives a reload");
+
+    fs::remove_dir_all(&cache)
+}
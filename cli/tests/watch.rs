@@ -0,0 +1,128 @@
+//! The watcher's change detection and report on a scratch day.
+
+mod common;
+
+use std::{
+    fs,
+    io,
+    path::PathBuf,
+    thread,
+    time::Duration,
+};
+
+use aoc_cli::{
+    runner::Example,
+    scaffold::{self, Template},
+    watch::{self, Snapshot},
+};
+use common::checkout::Checkout;
+
+/// A checkout with a day generated, and what the watcher looks at for it.
+fn watched_day() -> io::Result<(Checkout, PathBuf, Vec<PathBuf>)> {
+    let checkout = Checkout::new()?;
+
+    scaffold::new_day(&checkout.root, 2025, 1, Template::Lines)?;
+
+    let day_dir = checkout.day(2025, 1);
+    let watched = watch::watched(&checkout.root, 2025, 1);
+
+    Ok((checkout, day_dir, watched))
+}
+
+/// Modification times may be coarse, makes sure the writes that follow land on a later one.
+fn tick() {
+    thread::sleep(Duration::from_millis(20));
+}
+
+#[test]
+fn reports_the_example_then_the_input() -> io::Result<()> {
+    let (checkout, day_dir, _) = watched_day()?;
+
+    fs::write(Example::input_path(&day_dir), "1\n2\n3\n")?;
+    Example::write_answers(&day_dir, &[Some("6".to_owned()), None])?;
+
+    let report = watch::report(&checkout.root, 2025, 1);
+
+    assert!(report.contains("example  a  6  pass"), "a known example answer passes");
+    assert!(report.contains("example  b  6\n"), "an unknown example answer is shown");
+    assert!(!report.contains("input"), "a day without an input only runs its example");
+
+    Example::write_answers(&day_dir, &[Some("7".to_owned()), None])?;
+    fs::write(day_dir.join("input"), "10\n20\n")?;
+
+    let report = watch::report(&checkout.root, 2025, 1);
+
+    assert!(report.contains("example  a  6  FAIL"), "a wrong example answer fails");
+    assert!(report.contains("- 7\n") && report.contains("+ 6\n"), "a failure shows the expected and actual answers");
+    assert!(report.contains("input    a  30"), "the input runs after the example");
+
+    Ok(())
+}
+
+#[test]
+fn notices_fixtures_but_not_build_output() -> io::Result<()> {
+    let (_checkout, day_dir, watched) = watched_day()?;
+    let before = Snapshot::take(&watched);
+
+    assert!(before.changes(&Snapshot::take(&watched)).is_empty(), "nothing changes while nothing is written");
+
+    tick();
+    Example::write_answers(&day_dir, &[Some("7".to_owned()), None])?;
+    fs::write(day_dir.join("input"), "10\n20\n")?;
+    fs::create_dir_all(day_dir.join("a/target"))?;
+    fs::write(day_dir.join("a/target/ignored"), "")?;
+    fs::write(day_dir.join("a/Cargo.lock"), "")?;
+
+    assert!(
+        Snapshot::take(&watched).changes(&before) == [day_dir.join("example.answers"), day_dir.join("input")],
+        "new and modified fixtures are noticed, build output and lock files are not"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn notices_removed_files() -> io::Result<()> {
+    let (_checkout, day_dir, watched) = watched_day()?;
+    let before = Snapshot::take(&watched);
+
+    fs::remove_file(Example::input_path(&day_dir))?;
+
+    assert!(Snapshot::take(&watched).changes(&before) == [day_dir.join("example")], "removed files are noticed");
+
+    Ok(())
+}
+
+#[test]
+fn notices_edits_to_crates_the_parts_share() -> io::Result<()> {
+    let (_checkout, day_dir, watched) = watched_day()?;
+    let library = day_dir.join("dial/src/lib.rs");
+
+    fs::create_dir_all(library.parent().unwrap())?;
+    fs::write(day_dir.join("dial/Cargo.toml"), "[package]\nname = \"dial\"\n")?;
+    fs::write(&library, "pub fn turn() {}\n")?;
+
+    let before = Snapshot::take(&watched);
+
+    tick();
+    fs::write(&library, "pub fn turn() -> u32 {\n    0\n}\n")?;
+
+    assert!(Snapshot::take(&watched).changes(&before) == [library], "a library next to the parts is watched");
+
+    Ok(())
+}
+
+#[test]
+fn notices_the_sealed_input_and_answers() -> io::Result<()> {
+    let (_checkout, day_dir, watched) = watched_day()?;
+    let before = Snapshot::take(&watched);
+    let sealed = [day_dir.join("answers.enc"), day_dir.join("input.enc")];
+
+    for path in &sealed {
+        fs::write(path, "sealed")?;
+    }
+
+    assert!(Snapshot::take(&watched).changes(&before) == sealed, "sealed files are watched");
+
+    Ok(())
+}